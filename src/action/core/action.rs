@@ -3,13 +3,13 @@ use std::rc::Rc;
 use std::cell::RefCell;
 use ::node::{ NodeLike };
 use ::action::{ ActionDelegate, ActionStatus, ActionLike, ActionId };
+use ::util::{ director };
 use ::util::easing::{ EasingFunction };
-use time::{ Tm };
 
 pub struct Action<T> where T: ActionDelegate + Any {
     delegate: T,
     status: RefCell<ActionStatus>,
-    prev_tm: RefCell<Option<Tm>>,
+    prev_tm: RefCell<Option<i64>>,
     progress: RefCell<f32>,
     duration: f64,
     speed: RefCell<f64>
@@ -23,8 +23,8 @@ impl <T> ActionLike for Action<T> where T: ActionDelegate + Any {
 
     fn run(&self, node: Rc<dyn NodeLike>, easing: Option<Rc<dyn EasingFunction>>) -> ActionStatus {
         if self.initialize() {
-            let now = time::now();
-            let time_progress = self.generate_progress(&now);
+            let now = director::get_clock_time();
+            let time_progress = self.generate_progress(now);
             let progress = easing.map(|e| e.ease(time_progress)).unwrap_or(time_progress);
             let status = self.delegate.run(node, progress);
             if time_progress == 1.0 || (status.is_some() && status.unwrap() == ActionStatus::Finish) {
@@ -69,7 +69,7 @@ impl <T> ActionLike for Action<T> where T: ActionDelegate + Any {
                 if self.prev_tm.borrow().is_none() {
                     self.status.replace(ActionStatus::Wait);
                 } else {
-                    self.prev_tm.replace(Some(time::now()));
+                    self.prev_tm.replace(Some(director::get_clock_time()));
                     self.status.replace(ActionStatus::Processing);
                 }
             },
@@ -86,10 +86,10 @@ impl <T> Action<T> where T: ActionDelegate + Any {
         Rc::new(Self::new(duration, delegate))
     }
 
-    fn generate_progress(&self, now: &Tm) -> f32 {
+    fn generate_progress(&self, now: i64) -> f32 {
         if self.duration == 0.0 { return 1.0; }
         if let Some(prev_tm) = self.prev_tm.borrow().clone() {
-            let d = (*now - prev_tm).num_microseconds().unwrap();
+            let d = now - prev_tm;
             let dt = ((d as f64 / (self.duration * 1_000_000.0)) * self.get_speed()) as f32;
             let progress = self.progress.borrow().clone() + dt;
             if progress > 1.0 { 1.0 } else { progress }
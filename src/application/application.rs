@@ -1,8 +1,10 @@
 use std::rc::Rc;
 use ::node::scene::{ SceneLike };
-use ::application::{ ResolutionPolicy };
+use ::application::{ ResolutionPolicy, StepMode, WindowMode, AudioMode };
 use ::util::{ director };
 use ::util::parameter::{ Size };
+use serde_json::{ Value };
+use serde_json::map::Map;
 
 pub trait Application {
 
@@ -14,8 +16,14 @@ pub trait Application {
 
     fn application_will_enter_foreground(&self) { }
 
+    fn pause_on_focus_loss(&self) -> bool { false }
+
     fn fps(&self) -> u32 { 60 }
 
+    fn headless(&self) -> bool { false }
+
+    fn step_mode(&self) -> StepMode { StepMode::Variable }
+
     fn version(&self) -> String { "1.0.0".to_owned() }
 
     fn title(&self) -> String;
@@ -24,6 +32,20 @@ pub trait Application {
         Size::new(800, 600)
     }
 
+    fn window_mode(&self) -> WindowMode {
+        WindowMode::Windowed
+    }
+
+    fn resizable(&self) -> bool { false }
+
+    fn audio_mode(&self) -> AudioMode { AudioMode::Mixer }
+
+    fn default_config(&self) -> Value {
+        Value::Object(Map::new())
+    }
+
+    fn encrypt_config(&self) -> bool { false }
+
     fn resolution_size(&self) -> Size {
         self.window_size()
     }
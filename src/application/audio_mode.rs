@@ -0,0 +1,5 @@
+#[derive(Clone, Eq, PartialEq, Hash, Copy)]
+pub enum AudioMode {
+    Mixer,
+    Null
+}
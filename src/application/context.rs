@@ -1,11 +1,14 @@
+use std::env;
 use std::rc::Rc;
 use std::collections::HashMap;
-use ::application::{ Application };
+use ::application::{ Application, WindowMode, AudioMode };
 use ::resource::{ ResourceKey };
+use ::util::{ output_warning_log };
 use ::util::parameter::{ Size };
 use sdl2::render::{ Canvas, TextureCreator };
-use sdl2::video::{ WindowContext, Window };
-use sdl2::{ EventPump };
+use sdl2::video::{ WindowContext, Window, FullscreenType };
+use sdl2::{ Sdl, EventPump, GameControllerSubsystem };
+use sdl2::controller::{ GameController };
 use sdl2::ttf::{ Sdl2TtfContext };
 use sdl2::render::{ Texture, BlendMode };
 use sdl2::pixels::{ PixelFormatEnum, Color };
@@ -14,6 +17,9 @@ use sdl2::rwops::{ RWops };
 pub struct Context<'a> {
     pub canvas: Canvas<Window>,
     pub event_pump: EventPump,
+    pub game_controller_subsystem: GameControllerSubsystem,
+    pub game_controllers: HashMap<i32, GameController>,
+    pub audio_mode: AudioMode,
     pub texture_creator: TextureCreator<WindowContext>,
     pub ttf_context: Sdl2TtfContext,
     pub static_datas: HashMap<ResourceKey, Box<[u8]>>,
@@ -22,39 +28,88 @@ pub struct Context<'a> {
 
 impl <'a> Context<'a> {
 
-    pub fn new(application: Rc<dyn Application>) -> Self {
-        let (canvas, event_pump) = Self::build(application);
+    pub fn new(application: Rc<dyn Application>) -> Result<Self, String> {
+        let headless = application.headless();
+        Self::new_with_mode(application, headless)
+    }
+
+    pub fn new_with_mode(application: Rc<dyn Application>, headless: bool) -> Result<Self, String> {
+        let (canvas, event_pump, game_controller_subsystem, audio_mode) = if headless { Self::build_headless(application)? } else { Self::build(application) };
         let texture_creator = canvas.texture_creator();
-        Self {
+        Ok(Self {
             canvas: canvas,
             event_pump: event_pump,
+            game_controller_subsystem: game_controller_subsystem,
+            game_controllers: HashMap::new(),
+            audio_mode: audio_mode,
             texture_creator: texture_creator,
-            ttf_context: sdl2::ttf::init().unwrap(),
+            ttf_context: sdl2::ttf::init().map_err(|e| e.to_string())?,
             static_datas: HashMap::new(),
             static_rwops: HashMap::new()
-        }
+        })
     }
 
-    fn build(application: Rc<dyn Application>) -> (Canvas<Window>, EventPump) {
+    fn build(application: Rc<dyn Application>) -> (Canvas<Window>, EventPump, GameControllerSubsystem, AudioMode) {
         let sdl_context = sdl2::init().unwrap();
-        sdl_context.audio().unwrap();
-        sdl2::mixer::init(sdl2::mixer::InitFlag::all()).unwrap();
-        sdl2::mixer::open_audio(22050, sdl2::mixer::AUDIO_S16SYS, 2, 4096).unwrap();
+        let audio_mode = match application.audio_mode() {
+            AudioMode::Mixer => match Self::open_audio(&sdl_context) {
+                Ok(_) => AudioMode::Mixer,
+                Err(e) => {
+                    output_warning_log(&format!("failed to open audio, falling back to null audio: {}", e));
+                    AudioMode::Null
+                }
+            },
+            AudioMode::Null => AudioMode::Null
+        };
         let video_subsystem = sdl_context.video().unwrap();
         let window_size = application.window_size();
-        let window = video_subsystem
-            .window(application.title().as_str(), window_size.width(), window_size.height())
-            .opengl()
-            .position_centered()
-            .build()
-            .unwrap();
+        let mut window_builder = video_subsystem.window(application.title().as_str(), window_size.width(), window_size.height());
+        window_builder.opengl().position_centered();
+        if application.resizable() { window_builder.resizable(); }
+        let mut window = window_builder.build().unwrap();
+        if let Err(e) = window.set_fullscreen(Self::fullscreen_type(application.window_mode())) {
+            output_warning_log(&format!("failed to apply window mode, falling back to windowed: {}", e));
+            window.set_fullscreen(FullscreenType::Off).ok();
+        }
         let gl = Self::find_sdl_gl_driver().unwrap();
         (
             window.into_canvas().index(gl).build().unwrap(),
-            sdl_context.event_pump().unwrap()
+            sdl_context.event_pump().unwrap(),
+            sdl_context.game_controller().unwrap(),
+            audio_mode
         )
     }
 
+    fn build_headless(application: Rc<dyn Application>) -> Result<(Canvas<Window>, EventPump, GameControllerSubsystem, AudioMode), String> {
+        env::set_var("SDL_VIDEODRIVER", "dummy");
+        let sdl_context = sdl2::init()?;
+        let video_subsystem = sdl_context.video()?;
+        let window_size = application.window_size();
+        let window = video_subsystem
+            .window(application.title().as_str(), window_size.width(), window_size.height())
+            .hidden()
+            .build()
+            .map_err(|e| e.to_string())?;
+        Ok((
+            window.into_canvas().software().target_texture().build().map_err(|e| e.to_string())?,
+            sdl_context.event_pump()?,
+            sdl_context.game_controller()?,
+            AudioMode::Null
+        ))
+    }
+
+    fn open_audio(sdl_context: &Sdl) -> Result<(), String> {
+        sdl_context.audio()?;
+        sdl2::mixer::init(sdl2::mixer::InitFlag::OGG | sdl2::mixer::InitFlag::MP3)?;
+        sdl2::mixer::open_audio(22050, sdl2::mixer::AUDIO_S16SYS, 2, 4096)
+    }
+
+    pub fn close_audio(&self) {
+        if self.audio_mode == AudioMode::Mixer {
+            sdl2::mixer::close_audio();
+        }
+    }
+
     fn find_sdl_gl_driver() -> Result<u32, String> {
         for (index, item) in sdl2::render::drivers().enumerate() {
             if item.name == "opengl" {
@@ -64,6 +119,46 @@ impl <'a> Context<'a> {
         Err("OpenGL の初期化に失敗しました".to_owned())
     }
 
+    fn fullscreen_type(window_mode: WindowMode) -> FullscreenType {
+        match window_mode {
+            WindowMode::Windowed => FullscreenType::Off,
+            WindowMode::Fullscreen => FullscreenType::True,
+            WindowMode::BorderlessFullscreen => FullscreenType::Desktop
+        }
+    }
+
+    pub fn get_window_mode(&self) -> WindowMode {
+        match self.canvas.window().fullscreen_state() {
+            FullscreenType::Off => WindowMode::Windowed,
+            FullscreenType::True => WindowMode::Fullscreen,
+            FullscreenType::Desktop => WindowMode::BorderlessFullscreen
+        }
+    }
+
+    pub fn set_window_mode(&mut self, window_mode: WindowMode) -> Result<(), String> {
+        self.canvas.window_mut().set_fullscreen(Self::fullscreen_type(window_mode))
+    }
+
+    pub fn get_window_size(&self) -> Size {
+        let (width, height) = self.canvas.window().size();
+        Size::new(width, height)
+    }
+
+    pub fn set_window_size(&mut self, size: Size) -> Result<(), String> {
+        self.canvas.window_mut().set_size(size.width(), size.height()).map_err(|e| e.to_string())
+    }
+
+    pub fn open_game_controller(&mut self, index: u32) -> Option<i32> {
+        let controller = self.game_controller_subsystem.open(index).ok()?;
+        let instance_id = controller.instance_id();
+        self.game_controllers.insert(instance_id, controller);
+        Some(instance_id)
+    }
+
+    pub fn close_game_controller(&mut self, instance_id: i32) {
+        self.game_controllers.remove(&instance_id);
+    }
+
     pub fn get_static_data(&self, resource_key: &ResourceKey) -> Option<&Box<[u8]>> {
         self.static_datas.get(resource_key)
     }
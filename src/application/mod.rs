@@ -1,7 +1,13 @@
 mod application;
 mod resolution_policy;
+mod step_mode;
+mod window_mode;
+mod audio_mode;
 mod context;
 
 pub use self::application::*;
 pub use self::resolution_policy::*;
+pub use self::step_mode::*;
+pub use self::window_mode::*;
+pub use self::audio_mode::*;
 pub use self::context::*;
\ No newline at end of file
@@ -0,0 +1,6 @@
+#[derive(Clone, Eq, PartialEq, Hash, Copy, Serialize, Deserialize)]
+pub enum WindowMode {
+    Windowed,
+    Fullscreen,
+    BorderlessFullscreen
+}
@@ -4,9 +4,9 @@ use ::application::{ Application };
 use ::node::scene::{ SceneLike };
 use ::node::scene::transition::{ SceneTransition, TransitionNone };
 use ::node::label::{ LabelOption };
+use ::util::{ RandStream, RandSnapshot };
 use ::util::parameter::{ Size };
 use rand::{ Rng };
-use rand::rngs::{ ThreadRng };
 use rand::distributions::{ Standard, Distribution };
 
 pub struct ApplicationDirector {
@@ -16,8 +16,10 @@ pub struct ApplicationDirector {
     application: Option<Rc<dyn Application>>,
     label_option_aliases: HashMap<String, LabelOption>,
     current_fps: usize,
-    rand: ThreadRng,
-    continuing: bool
+    rand_seed: u64,
+    rands: HashMap<String, RandStream>,
+    continuing: bool,
+    background: bool
 }
 
 impl ApplicationDirector {
@@ -30,21 +32,54 @@ impl ApplicationDirector {
             application: None,
             label_option_aliases: HashMap::new(),
             current_fps: 0,
-            rand: rand::thread_rng(),
-            continuing: true
+            rand_seed: rand::thread_rng().gen(),
+            rands: HashMap::new(),
+            continuing: true,
+            background: false
         }
     }
 
-    pub fn window_size(&self) -> Size {
-        self.application().window_size()
-    }
-
     pub fn get_resolution_size(&self) -> Size {
         self.application().resolution_size()
     }
 
-    pub fn rand<T>(&mut self) -> T where Standard: Distribution<T> {
-        self.rand.gen()
+    pub fn rand<T>(&mut self, stream: &str) -> T where Standard: Distribution<T> {
+        let seed = self.rand_seed;
+        self.rands.entry(stream.to_owned())
+            .or_insert_with(|| RandStream::from_stream_name(seed, stream))
+            .gen()
+    }
+
+    pub fn seed_rand(&mut self, seed: u64) {
+        self.rand_seed = seed;
+        self.rands = HashMap::new();
+    }
+
+    pub fn seed_rand_stream(&mut self, stream: &str, seed: u64) {
+        self.rands.insert(stream.to_owned(), RandStream::new(seed));
+    }
+
+    pub fn get_rand_seed(&self) -> u64 {
+        self.rand_seed
+    }
+
+    pub fn snapshot_rand(&self) -> RandSnapshot {
+        let mut streams: HashMap<String, u64> = HashMap::new();
+        for (name, stream) in &self.rands {
+            streams.insert(name.to_string(), stream.state());
+        }
+        RandSnapshot {
+            seed: self.rand_seed,
+            streams: streams
+        }
+    }
+
+    pub fn restore_rand(&mut self, snapshot: &RandSnapshot) {
+        self.rand_seed = snapshot.seed;
+        self.rands = HashMap::new();
+        for (name, state) in &snapshot.streams {
+            self.rands.insert(name.to_string(), RandStream::new(*state));
+        }
     }
 
     pub fn is_continuing(&self) -> bool {
@@ -55,6 +90,14 @@ impl ApplicationDirector {
         self.continuing = continuing;
     }
 
+    pub fn is_background(&self) -> bool {
+        self.background
+    }
+
+    pub fn set_background(&mut self, background: bool) {
+        self.background = background;
+    }
+
     pub fn default_label_option(&self) -> Option<LabelOption> {
         self.label_option_aliases.get("").cloned()
     }
@@ -0,0 +1,103 @@
+pub struct ClockDirector {
+    now: i64,
+    delta: i64,
+    manual: bool,
+    paused: bool,
+    suspended: bool
+}
+
+impl ClockDirector {
+
+    pub fn new() -> Self {
+        Self {
+            now: 0,
+            delta: 0,
+            manual: false,
+            paused: false,
+            suspended: false
+        }
+    }
+
+    pub fn now(&self) -> i64 {
+        self.now
+    }
+
+    pub fn delta(&self) -> i64 {
+        self.delta
+    }
+
+    pub fn is_manual(&self) -> bool {
+        self.manual
+    }
+
+    pub fn set_manual(&mut self, manual: bool) {
+        self.manual = manual;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused || self.suspended
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    pub fn is_suspended(&self) -> bool {
+        self.suspended
+    }
+
+    pub fn set_suspended(&mut self, suspended: bool) {
+        self.suspended = suspended;
+    }
+
+    pub fn tick(&mut self, delta: i64) {
+        if self.manual { return; }
+        self.advance(if self.is_paused() { 0 } else { delta });
+    }
+
+    pub fn replay(&mut self, elapsed: i64, delta: i64) {
+        self.delta = delta.max(0);
+        self.now += elapsed.max(0);
+    }
+
+    pub fn advance(&mut self, delta: i64) {
+        let delta = delta.max(0);
+        self.delta = delta;
+        self.now += delta;
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn negative_deltas_are_clamped() {
+        let mut clock = ClockDirector::new();
+        clock.tick(1_000);
+        clock.tick(-500);
+        assert_eq!(clock.now(), 1_000);
+        assert_eq!(clock.delta(), 0);
+        clock.replay(-1, -1);
+        assert_eq!(clock.now(), 1_000);
+        assert_eq!(clock.delta(), 0);
+    }
+
+    #[test]
+    fn paused_and_manual_clocks_do_not_tick() {
+        let mut clock = ClockDirector::new();
+        clock.set_paused(true);
+        clock.tick(1_000);
+        assert_eq!(clock.now(), 0);
+        assert!(!clock.is_suspended());
+        clock.set_paused(false);
+        clock.set_manual(true);
+        clock.tick(1_000);
+        assert_eq!(clock.now(), 0);
+        clock.advance(250);
+        assert_eq!(clock.now(), 250);
+    }
+
+}
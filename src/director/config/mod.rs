@@ -0,0 +1,120 @@
+use std::rc::Rc;
+use ::application::{ Application };
+use ::resource::{ FileStorage };
+use ::util::{ ENCRYPT_KEY, exe_dir };
+use serde_json::{ Value };
+use serde_json::map::Map;
+use serde::de::DeserializeOwned;
+use serde::ser::Serialize;
+
+const CONFIG_FILE_NAME: &'static str = "settings.json";
+
+pub const CONFIG_WINDOW_MODE: &'static str = "window_mode";
+
+pub const CONFIG_INPUT_CODE_MAP: &'static str = "input_code_map";
+
+pub const CONFIG_VOLUME_MASTER: &'static str = "volume.master";
+
+pub const CONFIG_VOLUME_BGM: &'static str = "volume.bgm";
+
+pub const CONFIG_VOLUME_SE: &'static str = "volume.se";
+
+pub const CONFIG_VOLUME_VOICE: &'static str = "volume.voice";
+
+pub struct ConfigDirector {
+    values: Map<String, Value>,
+    defaults: Map<String, Value>,
+    storage: FileStorage,
+    dirty: bool
+}
+
+impl ConfigDirector {
+
+    pub fn new() -> Self {
+        Self {
+            values: Map::new(),
+            defaults: Map::new(),
+            storage: FileStorage::new(Self::save_dir(), None),
+            dirty: false
+        }
+    }
+
+    fn save_dir() -> ::std::path::PathBuf {
+        let mut save_dir = exe_dir();
+        save_dir.push("save");
+        save_dir
+    }
+
+    pub fn set_application(&mut self, application: Rc<dyn Application>) {
+        let encrypt_key = if application.encrypt_config() { Some(ENCRYPT_KEY.to_owned()) } else { None };
+        self.storage = FileStorage::new(Self::save_dir(), encrypt_key);
+        self.defaults = application.default_config().as_object().cloned().unwrap_or(Map::new());
+        self.load();
+    }
+
+    pub fn load(&mut self) {
+        self.values = self.storage.load(CONFIG_FILE_NAME).ok()
+            .and_then(|bytes| serde_json::from_slice::<Value>(&bytes).ok())
+            .and_then(|v| v.as_object().cloned())
+            .unwrap_or(Map::new());
+        self.dirty = false;
+    }
+
+    pub fn save(&mut self) -> Result<(), String> {
+        let data = serde_json::to_string_pretty(&self.values).map_err(|e| e.to_string())?;
+        self.storage.save(CONFIG_FILE_NAME, &data.as_bytes().to_vec())?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<(), String> {
+        if !self.dirty { return Ok(()); }
+        self.save()
+    }
+
+    pub fn get<T>(&self, key: &str) -> Option<T> where T: DeserializeOwned {
+        self.values.get(key).or_else(|| self.defaults.get(key))
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+    }
+
+    pub fn set<T>(&mut self, key: &str, value: &T) -> Result<bool, String> where T: Serialize {
+        let changed = self.set_deferred(key, value)?;
+        if changed { self.save()?; }
+        Ok(changed)
+    }
+
+    pub fn set_deferred<T>(&mut self, key: &str, value: &T) -> Result<bool, String> where T: Serialize {
+        let v = serde_json::to_value(value).map_err(|e| e.to_string())?;
+        if self.values.get(key) == Some(&v) { return Ok(false); }
+        self.values.insert(key.to_owned(), v);
+        self.dirty = true;
+        Ok(true)
+    }
+
+    pub fn reset(&mut self, key: &str) -> Result<(), String> {
+        if self.values.remove(key).is_none() { return Ok(()); }
+        self.save()
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn deferred_values_are_saved_on_flush() {
+        let mut config = ConfigDirector::new();
+        config.storage = FileStorage::new(::std::env::temp_dir().join("rgengine-config-test"), None);
+        assert!(config.set_deferred(CONFIG_VOLUME_MASTER, &0.5).unwrap());
+        assert!(!config.set_deferred(CONFIG_VOLUME_MASTER, &0.5).unwrap());
+        assert!(config.dirty);
+        assert_eq!(config.get::<f32>(CONFIG_VOLUME_MASTER), Some(0.5));
+        config.flush().unwrap();
+        assert!(!config.dirty);
+        config.load();
+        assert_eq!(config.get::<f32>(CONFIG_VOLUME_MASTER), Some(0.5));
+    }
+
+}
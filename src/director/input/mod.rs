@@ -1,18 +1,33 @@
-use std::collections::HashMap;
-use ::util::parameter::{ Point, InputCodeMap, InputCode, InputInfo };
-use sdl2::{ EventPump };
+use std::collections::{ HashMap, BTreeMap };
+use ::resource::{ FileStorage };
+use ::util::{ exe_dir };
+use ::util::parameter::{ Point, InputCodeMap, InputCode, InputInfo, InputEvent, InputFrame, InputRecording, PressState, StickDirection, TextInputInfo, TouchPoint, TouchGesture, Size };
 use sdl2::keyboard::Keycode;
-use sdl2::mouse::{ MouseButton, MouseWheelDirection };
-use sdl2::event::Event;
+use sdl2::mouse::{ MouseButton };
 use sdl2::joystick::HatState;
+use sdl2::controller::{ Button, Axis };
 use std::convert::TryInto;
 
+const CONTROLLER_TRIGGER_THRESHOLD: i16 = 16384;
+
+const STICK_DIRECTION_THRESHOLD: f32 = 0.5;
+
+const TOUCH_SLOP: f32 = 10.0;
+
+const LONG_PRESS_TIME: i64 = 500_000;
+
+const SWIPE_DISTANCE: f32 = 50.0;
+
+const SWIPE_TIME: i64 = 500_000;
+
 #[derive(Clone)]
 struct InputState {
     mouse_pointer: Point,
     mouses: HashMap<MouseButton, bool>,
-    mousewheels: HashMap<MouseWheelDirection, bool>,
+    mouse_motion: Point,
+    wheel_delta: Point,
     joystikcs: HashMap<i32, JoystickState>,
+    controllers: HashMap<u32, ControllerState>,
     keys: HashMap<Keycode, bool>,
 }
 
@@ -22,8 +37,10 @@ impl InputState {
         Self {
             mouse_pointer: Point::new(-1, -1),
             mouses: HashMap::new(),
-            mousewheels: HashMap::new(),
+            mouse_motion: Point::new(0, 0),
+            wheel_delta: Point::new(0, 0),
             joystikcs: HashMap::new(),
+            controllers: HashMap::new(),
             keys: HashMap::new()
         }
     }
@@ -46,7 +63,7 @@ impl InputState {
 struct JoystickState {
     buttons: HashMap<u8, bool>,
     hats: HashMap<HatState, bool>,
-    sticks: Point
+    axes: HashMap<u8, i16>
 }
 
 impl JoystickState {
@@ -55,62 +72,234 @@ impl JoystickState {
         Self {
             buttons: HashMap::new(),
             hats: HashMap::new(),
-            sticks: Point::new(0, 0)
+            axes: HashMap::new()
         }
     }
 
     fn reset(&mut self) {
-        self.sticks = Point::new(0, 0);
         self.hats = HashMap::new()
     }
 
 }
 
+#[derive(Clone)]
+struct ControllerState {
+    buttons: HashMap<Button, bool>,
+    axes: HashMap<Axis, i16>
+}
+
+impl ControllerState {
+
+    fn new() -> Self {
+        Self {
+            buttons: HashMap::new(),
+            axes: HashMap::new()
+        }
+    }
+
+}
+
+#[derive(Clone)]
+struct KeyTracker {
+    held: i64,
+    next_repeat: i64,
+    repeat: bool,
+    steps_since_press: Option<u32>
+}
+
+impl KeyTracker {
+
+    fn new() -> Self {
+        Self {
+            held: 0,
+            next_repeat: 0,
+            repeat: false,
+            steps_since_press: None
+        }
+    }
+
+    fn update(&mut self, info: &InputInfo, elapsed: i64, key_repeat: (i64, i64)) {
+        self.repeat = false;
+        if info.is_press_start() {
+            self.held = 0;
+            self.next_repeat = key_repeat.0;
+            self.repeat = true;
+            self.steps_since_press = Some(0);
+            return;
+        }
+        self.steps_since_press = self.steps_since_press.map(|s| s.saturating_add(1));
+        if info.is_pressing() {
+            self.held += elapsed;
+            if self.held >= self.next_repeat {
+                self.repeat = true;
+                self.next_repeat += key_repeat.1;
+            }
+        } else {
+            self.held = 0;
+        }
+    }
+
+}
+
+#[derive(Clone)]
+struct TouchState {
+    start_position: (f32, f32),
+    position: (f32, f32),
+    held: i64,
+    press_start: bool,
+    released: bool,
+    moved: bool,
+    long_pressed: bool
+}
+
+impl TouchState {
+
+    fn new(position: (f32, f32)) -> Self {
+        Self {
+            start_position: position,
+            position: position,
+            held: 0,
+            press_start: true,
+            released: false,
+            moved: false,
+            long_pressed: false
+        }
+    }
+
+    fn distance(&self) -> f32 {
+        ((self.position.0 - self.start_position.0).powi(2) + (self.position.1 - self.start_position.1).powi(2)).sqrt()
+    }
+
+    fn to_touch_point(&self, id: i64) -> TouchPoint {
+        TouchPoint {
+            id: id,
+            position: to_point(self.position),
+            start_position: to_point(self.start_position),
+            press_start: self.press_start,
+            pressed: self.released,
+            duration: self.held
+        }
+    }
+
+}
+
+fn to_point(position: (f32, f32)) -> Point {
+    Point::new(position.0.round() as i32, position.1.round() as i32)
+}
+
+#[derive(Clone)]
+struct InputContext {
+    name: String,
+    pass_through: bool,
+    closing: bool
+}
+
 pub struct InputDirector {
     quit: bool,
     input_code_map: InputCodeMap,
     prev_state: InputState,
-    state: InputState
+    state: InputState,
+    storage: FileStorage,
+    capturing: bool,
+    captured_input: Option<InputCode>,
+    pending_events: Vec<InputEvent>,
+    injected_events: Vec<InputEvent>,
+    controller_players: HashMap<i32, u32>,
+    stick_deadzone: f32,
+    key_repeat: (i64, i64),
+    key_trackers: HashMap<String, KeyTracker>,
+    contexts: Vec<InputContext>,
+    text_input: Option<TextInputInfo>,
+    window_size: Size,
+    touches: BTreeMap<i64, TouchState>,
+    pinch: Option<(f32, f32)>,
+    gestures: Vec<TouchGesture>,
+    recording: Option<InputRecording>,
+    replay: Option<InputRecording>,
+    replay_frame: usize,
+    update_time: i64
 }
 
 impl InputDirector {
 
     pub fn new() -> Self {
+        let mut save_dir = exe_dir();
+        save_dir.push("save");
         Self {
             quit: false,
+            storage: FileStorage::new(save_dir, None),
+            capturing: false,
+            captured_input: None,
             input_code_map: InputCodeMap::new(),
             prev_state: InputState::new(),
-            state: InputState::new()
+            state: InputState::new(),
+            pending_events: Vec::new(),
+            injected_events: Vec::new(),
+            controller_players: HashMap::new(),
+            stick_deadzone: 0.2,
+            key_repeat: (400_000, 80_000),
+            key_trackers: HashMap::new(),
+            contexts: Vec::new(),
+            text_input: None,
+            window_size: Size::new(0, 0),
+            touches: BTreeMap::new(),
+            pinch: None,
+            gestures: Vec::new(),
+            recording: None,
+            replay: None,
+            replay_frame: 0,
+            update_time: 0
         }
     }
 
-    pub fn get_input_info<A>(&self, key: A) -> InputInfo
+    pub fn get_input_info<A>(&self, context: Option<&str>, key: A) -> InputInfo
     where A: Into<String>
     {
+        if !self.is_context_active(context) { return InputInfo::new(); }
+        let k = key.into();
+        let mut info = self.get_raw_input_info(&k);
+        if let Some(tracker) = self.key_trackers.get(&k) {
+            info.repeat = tracker.repeat;
+        }
+        info
+    }
+
+    fn get_raw_input_info(&self, key: &str) -> InputInfo {
         let mut info = InputInfo::new();
         for k in self.input_code_map.convert_key(key) {
             let keycode: Result<Keycode, String> = k.clone().try_into();
             if let Ok(k) = keycode {
-                info.update_press_state(self.get_keyboard_state(&k));
+                if self.text_input.is_none() || !Self::is_text_key(&k) {
+                    info.update_press_state(self.get_keyboard_state(&k));
+                }
                 continue;
             }
             let mouse: Result<MouseButton, String> = k.clone().try_into();
             if let Ok(k) = mouse {
                 let status = self.get_mouse_button_state(&k);
                 info.update_press_state(status.clone());
-                if status.0 || status.1 || status.2 { info.mouse_position = self.get_mouse_pointer(); }
+                if status.0 || status.1 || status.2 {
+                    info.mouse_position = self.get_mouse_pointer();
+                    info.mouse_motion = self.get_mouse_motion();
+                }
+                continue;
+            }
+            if let Some((player, button)) = Self::split_controller_button_code(&k) {
+                info.update_press_state(self.get_controller_button_state(player, button));
                 continue;
             }
             match k.clone() {
                 InputCode::MouseWheelUp => {
-                    let status = self.get_mouse_wheel_state(MouseWheelDirection::Normal);
-                    info.update_press_state(status.clone());
-                    if status.0 || status.1 || status.2 { info.mouse_position = self.get_mouse_pointer(); }
+                    self.update_mouse_wheel_info(&mut info, StickDirection::Up);
                 },
                 InputCode::MouseWheelDown => {
-                    let status = self.get_mouse_wheel_state(MouseWheelDirection::Flipped);
-                    info.update_press_state(status.clone());
-                    if status.0 || status.1 || status.2 { info.mouse_position = self.get_mouse_pointer(); }
+                    self.update_mouse_wheel_info(&mut info, StickDirection::Down);
+                },
+                InputCode::MouseWheelLeft => {
+                    self.update_mouse_wheel_info(&mut info, StickDirection::Left);
+                },
+                InputCode::MouseWheelRight => {
+                    self.update_mouse_wheel_info(&mut info, StickDirection::Right);
                 },
                 InputCode::JoystickButton { index, button } => {
                     info.update_press_state(self.get_joystick_button_state(index, button));
@@ -129,6 +318,45 @@ impl InputDirector {
                 },
                 InputCode::JoystickAxis { index } => {
                     info.axis_position = self.get_joystick_axis(index);
+                    info.update_axis_value(self.get_joystick_stick_value(&self.state, index, 0));
+                },
+                InputCode::JoystickStick { index, stick } => {
+                    info.update_axis_value(self.get_joystick_stick_value(&self.state, index, stick));
+                },
+                InputCode::JoystickStickDirection { index, stick, direction } => {
+                    let prev_value = self.get_joystick_stick_value(&self.prev_state, index, stick);
+                    let value = self.get_joystick_stick_value(&self.state, index, stick);
+                    info.update_press_state(Self::get_stick_direction_state(prev_value, value, direction));
+                },
+                InputCode::JoystickTrigger { index, axis } => {
+                    info.update_press_state(self.get_joystick_trigger_state(index, axis));
+                    info.update_trigger_value(self.get_joystick_trigger_value(index, axis));
+                },
+                InputCode::ControllerLeftTrigger { player } => {
+                    info.update_press_state(self.get_controller_trigger_state(player, Axis::TriggerLeft));
+                    info.update_trigger_value(self.get_controller_trigger_value(player, Axis::TriggerLeft));
+                },
+                InputCode::ControllerRightTrigger { player } => {
+                    info.update_press_state(self.get_controller_trigger_state(player, Axis::TriggerRight));
+                    info.update_trigger_value(self.get_controller_trigger_value(player, Axis::TriggerRight));
+                },
+                InputCode::ControllerLeftStick { player } => {
+                    info.axis_position = self.get_controller_stick(player, Axis::LeftX, Axis::LeftY);
+                    info.update_axis_value(self.get_controller_stick_value(&self.state, player, Axis::LeftX, Axis::LeftY));
+                },
+                InputCode::ControllerRightStick { player } => {
+                    info.axis_position = self.get_controller_stick(player, Axis::RightX, Axis::RightY);
+                    info.update_axis_value(self.get_controller_stick_value(&self.state, player, Axis::RightX, Axis::RightY));
+                },
+                InputCode::ControllerLeftStickDirection { player, direction } => {
+                    let prev_value = self.get_controller_stick_value(&self.prev_state, player, Axis::LeftX, Axis::LeftY);
+                    let value = self.get_controller_stick_value(&self.state, player, Axis::LeftX, Axis::LeftY);
+                    info.update_press_state(Self::get_stick_direction_state(prev_value, value, direction));
+                },
+                InputCode::ControllerRightStickDirection { player, direction } => {
+                    let prev_value = self.get_controller_stick_value(&self.prev_state, player, Axis::RightX, Axis::RightY);
+                    let value = self.get_controller_stick_value(&self.state, player, Axis::RightX, Axis::RightY);
+                    info.update_press_state(Self::get_stick_direction_state(prev_value, value, direction));
                 },
                 _ => {}
             }
@@ -148,90 +376,555 @@ impl InputDirector {
         self.input_code_map.reset(key);
     }
 
-    pub fn update_state(&mut self, events: &mut EventPump) {
+    pub fn remove_key_code<A>(&mut self, key: A, code: &InputCode)
+        where A: Into<String>
+    {
+        self.input_code_map.remove(key, code);
+    }
+
+    pub fn get_input_code_map(&self) -> InputCodeMap {
+        self.input_code_map.clone()
+    }
+
+    pub fn set_input_code_map(&mut self, input_code_map: InputCodeMap) {
+        self.input_code_map = input_code_map;
+    }
+
+    pub fn load_input_code_map(&mut self, name: &str) -> Result<(), String> {
+        let data = self.storage.load(name)?;
+        self.input_code_map = serde_json::from_slice(data.as_slice()).map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    pub fn save_input_code_map(&self, name: &str) -> Result<(), String> {
+        let data = serde_json::to_vec(&self.input_code_map).map_err(|e| e.to_string())?;
+        self.storage.save(name, &data)
+    }
+
+    pub fn start_capture(&mut self) {
+        self.capturing = true;
+        self.captured_input = None;
+    }
+
+    pub fn cancel_capture(&mut self) {
+        self.capturing = false;
+        self.captured_input = None;
+    }
+
+    pub fn is_capturing(&self) -> bool {
+        self.capturing
+    }
+
+    pub fn take_captured_input(&mut self) -> Option<InputCode> {
+        self.captured_input.take()
+    }
+
+    pub fn update_state(&mut self, events: Vec<InputEvent>) {
+        self.contexts.retain(|c| !c.closing);
         self.prev_state = self.state.clone();
-        self.state.mousewheels = HashMap::new();
+        self.state.mouse_motion = Point::new(0, 0);
+        self.state.wheel_delta = Point::new(0, 0);
         self.state.reset_joystick();
-        for event in events.poll_iter() {
-            match event {
-                Event::Quit { .. } => {
-                    self.quit = true;
-                },
-                Event::KeyDown { keycode, .. } => {
-                    if let Some(k) = &keycode {
-                        self.state.keys.insert(k.clone(), true);
-                    }
-                },
-                Event::KeyUp { keycode, .. } => {
-                    if let Some(k) = &keycode {
-                        self.state.keys.insert(k.clone(), false);
-                    }
-                },
-                Event::MouseMotion { x, y, .. } => {
-                    self.state.mouse_pointer = Point::new(x, y);
-                },
-                Event::MouseButtonDown { mouse_btn, .. } => {
-                    self.state.mouses.insert(mouse_btn.clone(), true);
-                },
-                Event::MouseButtonUp { mouse_btn, .. } => {
-                    self.state.mouses.insert(mouse_btn.clone(), false);
-                },
-                Event::MouseWheel { y, .. } => {
-                    if y == 1 {
-                        self.state.mousewheels.insert(MouseWheelDirection::Normal, true);
-                    } else {
-                        self.state.mousewheels.insert(MouseWheelDirection::Flipped, true);
-                    }
-                },
-                Event::JoyHatMotion { which, state, .. } => {
-                    self.state.init_joysticks(which);
-                    let j = self.state.joystikcs.get_mut(&which).unwrap();
-                    j.hats.insert(state, true);
-                },
-                Event::JoyButtonDown { which, button_idx, .. } => {
-                    self.state.init_joysticks(which);
-                    let j = self.state.joystikcs.get_mut(&which).unwrap();
-                    j.buttons.insert(button_idx, true);
-                },
-                Event::JoyButtonUp { which, button_idx, .. } => {
-                    self.state.init_joysticks(which);
-                    let j = self.state.joystikcs.get_mut(&which).unwrap();
-                    j.buttons.insert(button_idx, false);
-                },
-                Event::JoyAxisMotion { which, axis_idx, value, .. } => {
-                    self.state.init_joysticks(which);
-                    let j = self.state.joystikcs.get_mut(&which).unwrap();
-                    if axis_idx == 0 {
-                        j.sticks = Point::new(value as i32, j.sticks.y());
-                    }
-                    if axis_idx == 1 {
-                        j.sticks = Point::new(j.sticks.x(), value as i32);
+        self.touches = self.touches.iter().filter(|(_, t)| !t.released).map(|(id, t)| {
+            (*id, TouchState { press_start: false, ..t.clone() })
+        }).collect();
+        for event in events {
+            self.apply_event(event);
+        }
+    }
+
+    pub fn update_key_trackers(&mut self, elapsed: i64) {
+        let key_repeat = self.key_repeat;
+        for key in self.input_code_map.keys() {
+            let info = self.get_raw_input_info(&key);
+            self.key_trackers.entry(key).or_insert_with(KeyTracker::new).update(&info, elapsed, key_repeat);
+        }
+    }
+
+    pub fn set_key_repeat(&mut self, delay: i64, interval: i64) {
+        if delay < 0 || interval <= 0 { panic!(format!("invalid key repeat: {}, {}", delay, interval)); }
+        self.key_repeat = (delay, interval);
+    }
+
+    pub fn push_context(&mut self, name: &str, pass_through: bool) {
+        self.contexts.push(InputContext {
+            name: name.to_owned(),
+            pass_through: pass_through,
+            closing: false
+        });
+    }
+
+    pub fn pop_context(&mut self, name: &str) {
+        if let Some(c) = self.contexts.iter_mut().rev().find(|c| !c.closing && c.name == name) {
+            c.closing = true;
+        }
+    }
+
+    fn is_context_active(&self, context: Option<&str>) -> bool {
+        let start = match context {
+            Some(name) => {
+                match self.contexts.iter().rposition(|c| !c.closing && c.name == name) {
+                    Some(index) => index + 1,
+                    None => { return false; }
+                }
+            },
+            None => 0
+        };
+        self.contexts[start..].iter().all(|c| c.pass_through)
+    }
+
+    pub fn is_buffered(&self, context: Option<&str>, key: &str, steps: u32) -> bool {
+        if !self.is_context_active(context) { return false; }
+        self.key_trackers.get(key).and_then(|t| t.steps_since_press).map(|s| s <= steps).unwrap_or(false)
+    }
+
+    pub fn consume_buffered(&mut self, key: &str) {
+        if let Some(tracker) = self.key_trackers.get_mut(key) {
+            tracker.steps_since_press = None;
+        }
+    }
+
+    fn apply_event(&mut self, event: InputEvent) {
+        if self.capturing {
+            if let Some(code) = event.pressed_input_code().or_else(|| self.captured_controller_code(&event)) {
+                self.capturing = false;
+                self.captured_input = Some(code);
+                return;
+            }
+        }
+        match event {
+            InputEvent::Quit => {
+                self.quit = true;
+            },
+            InputEvent::KeyDown { keycode } => {
+                if let Some(k) = InputEvent::keycode(keycode) {
+                    if let Some(text_input) = &mut self.text_input {
+                        if k == Keycode::Backspace && text_input.composition.is_empty() && text_input.committed.pop().is_none() {
+                            text_input.backspaces += 1;
+                        }
                     }
-                },
-                Event::JoyDeviceRemoved { which, .. } => {
-                    self.state.joystikcs.remove(&which);
-                },
-                _ => {}
+                    self.state.keys.insert(k, true);
+                }
+            },
+            InputEvent::KeyUp { keycode } => {
+                if let Some(k) = InputEvent::keycode(keycode) {
+                    self.state.keys.insert(k, false);
+                }
+            },
+            InputEvent::MouseMotion { x, y, xrel, yrel } => {
+                let motion = self.state.mouse_motion.clone();
+                self.state.mouse_pointer = Point::new(x, y);
+                self.state.mouse_motion = Point::new(motion.x() + xrel, motion.y() + yrel);
+            },
+            InputEvent::MouseButtonDown { button } => {
+                self.state.mouses.insert(InputEvent::mouse_button(button), true);
+            },
+            InputEvent::MouseButtonUp { button } => {
+                self.state.mouses.insert(InputEvent::mouse_button(button), false);
+            },
+            InputEvent::MouseWheel { x, y } => {
+                let delta = self.state.wheel_delta.clone();
+                self.state.wheel_delta = Point::new(delta.x() + x, delta.y() + y);
+            },
+            InputEvent::JoyHatMotion { which, state } => {
+                self.state.init_joysticks(which);
+                let j = self.state.joystikcs.get_mut(&which).unwrap();
+                j.hats.insert(InputEvent::hat_state(state), true);
+            },
+            InputEvent::JoyButtonDown { which, button } => {
+                self.state.init_joysticks(which);
+                let j = self.state.joystikcs.get_mut(&which).unwrap();
+                j.buttons.insert(button, true);
+            },
+            InputEvent::JoyButtonUp { which, button } => {
+                self.state.init_joysticks(which);
+                let j = self.state.joystikcs.get_mut(&which).unwrap();
+                j.buttons.insert(button, false);
+            },
+            InputEvent::JoyAxisMotion { which, axis, value } => {
+                self.state.init_joysticks(which);
+                let j = self.state.joystikcs.get_mut(&which).unwrap();
+                j.axes.insert(axis, value);
+            },
+            InputEvent::JoyDeviceRemoved { which } => {
+                self.state.joystikcs.remove(&which);
+            },
+            InputEvent::ControllerDeviceAdded { which } => {
+                self.controller_player(which);
+            },
+            InputEvent::ControllerDeviceRemoved { which } => {
+                if let Some(player) = self.controller_players.remove(&which) {
+                    self.state.controllers.remove(&player);
+                }
+            },
+            InputEvent::ControllerButtonDown { which, button } => {
+                let player = self.controller_player(which);
+                let c = self.state.controllers.get_mut(&player).unwrap();
+                c.buttons.insert(InputEvent::controller_button(button), true);
+            },
+            InputEvent::ControllerButtonUp { which, button } => {
+                let player = self.controller_player(which);
+                let c = self.state.controllers.get_mut(&player).unwrap();
+                c.buttons.insert(InputEvent::controller_button(button), false);
+            },
+            InputEvent::ControllerAxisMotion { which, axis, value } => {
+                let player = self.controller_player(which);
+                let c = self.state.controllers.get_mut(&player).unwrap();
+                c.axes.insert(InputEvent::controller_axis(axis), value);
+            },
+            InputEvent::TextInput { text } => {
+                if let Some(text_input) = &mut self.text_input {
+                    text_input.committed.push_str(&text);
+                    text_input.composition = String::new();
+                    text_input.cursor = 0;
+                }
+            },
+            InputEvent::TextEditing { text, start, .. } => {
+                if let Some(text_input) = &mut self.text_input {
+                    text_input.composition = text;
+                    text_input.cursor = start;
+                }
+            },
+            InputEvent::FingerDown { finger_id, x, y } => {
+                let position = self.to_window_position(x, y);
+                self.touches.insert(finger_id, TouchState::new(position));
+            },
+            InputEvent::FingerMotion { finger_id, x, y } => {
+                let position = self.to_window_position(x, y);
+                if let Some(t) = self.touches.get_mut(&finger_id) {
+                    t.position = position;
+                }
+            },
+            InputEvent::FingerUp { finger_id, x, y } => {
+                let position = self.to_window_position(x, y);
+                if let Some(t) = self.touches.get_mut(&finger_id) {
+                    t.position = position;
+                    t.released = true;
+                }
+            }
+        }
+    }
+
+    pub fn set_window_size(&mut self, window_size: Size) {
+        self.window_size = window_size;
+    }
+
+    fn to_window_position(&self, x: f32, y: f32) -> (f32, f32) {
+        (x * self.window_size.width() as f32, y * self.window_size.height() as f32)
+    }
+
+    pub fn update_touches(&mut self, elapsed: i64) {
+        self.gestures = Vec::new();
+        self.update_pinch();
+        for t in self.touches.values_mut() {
+            if !t.press_start { t.held += elapsed; }
+            if t.distance() > TOUCH_SLOP { t.moved = true; }
+            if !t.moved && !t.long_pressed && !t.released && t.held >= LONG_PRESS_TIME {
+                t.long_pressed = true;
+                self.gestures.push(TouchGesture::LongPress { position: to_point(t.position) });
+            }
+            if !t.released || t.long_pressed { continue; }
+            if !t.moved {
+                self.gestures.push(TouchGesture::Tap { position: to_point(t.position) });
+            } else if t.distance() >= SWIPE_DISTANCE && t.held <= SWIPE_TIME {
+                let d = (t.position.0 - t.start_position.0, t.position.1 - t.start_position.1);
+                let direction = if d.0.abs() > d.1.abs() {
+                    if d.0 > 0.0 { StickDirection::Right } else { StickDirection::Left }
+                } else {
+                    if d.1 > 0.0 { StickDirection::Down } else { StickDirection::Up }
+                };
+                self.gestures.push(TouchGesture::Swipe {
+                    start: to_point(t.start_position),
+                    end: to_point(t.position),
+                    direction: direction
+                });
+            }
+        }
+    }
+
+    fn update_pinch(&mut self) {
+        let active: Vec<(f32, f32)> = self.touches.values().filter(|t| !t.released).map(|t| t.position).collect();
+        if active.len() != 2 {
+            self.pinch = None;
+            return;
+        }
+        let d = (active[1].0 - active[0].0, active[1].1 - active[0].1);
+        let distance = (d.0.powi(2) + d.1.powi(2)).sqrt();
+        let angle = d.1.atan2(d.0).to_degrees();
+        for t in self.touches.values_mut() {
+            t.moved = true;
+        }
+        match self.pinch {
+            None => { self.pinch = Some((distance, angle)); },
+            Some((start_distance, start_angle)) => {
+                let mut rotation = angle - start_angle;
+                if rotation > 180.0 { rotation -= 360.0; }
+                if rotation <= -180.0 { rotation += 360.0; }
+                self.gestures.push(TouchGesture::Pinch {
+                    center: to_point(((active[0].0 + active[1].0) / 2.0, (active[0].1 + active[1].1) / 2.0)),
+                    scale: if start_distance > 0.0 { distance / start_distance } else { 1.0 },
+                    rotation: rotation
+                });
             }
         }
     }
 
+    pub fn get_touches(&self) -> Vec<TouchPoint> {
+        self.touches.iter().map(|(id, t)| t.to_touch_point(*id)).collect()
+    }
+
+    pub fn get_touch_gestures(&self) -> Vec<TouchGesture> {
+        self.gestures.clone()
+    }
+
+    pub fn start_text_input(&mut self) {
+        if self.text_input.is_none() {
+            self.text_input = Some(TextInputInfo::new());
+        }
+    }
+
+    pub fn stop_text_input(&mut self) {
+        self.text_input = None;
+    }
+
+    pub fn is_text_input_active(&self) -> bool {
+        self.text_input.is_some()
+    }
+
+    pub fn take_text_input(&mut self) -> TextInputInfo {
+        match &mut self.text_input {
+            Some(text_input) => {
+                let info = text_input.clone();
+                text_input.committed = String::new();
+                text_input.backspaces = 0;
+                info
+            },
+            None => TextInputInfo::new()
+        }
+    }
+
+    fn controller_player(&mut self, instance_id: i32) -> u32 {
+        if let Some(player) = self.controller_players.get(&instance_id) {
+            return *player;
+        }
+        let mut player: u32 = if instance_id < 0 { (-1 - instance_id) as u32 } else { 0 };
+        while self.controller_players.values().any(|p| *p == player) { player += 1; }
+        self.controller_players.insert(instance_id, player);
+        self.state.controllers.insert(player, ControllerState::new());
+        player
+    }
+
+    fn controller_instance(&self, player: u32) -> i32 {
+        self.controller_players.iter()
+            .find(|(_, p)| **p == player)
+            .map(|(id, _)| *id)
+            .unwrap_or(-1 - player as i32)
+    }
+
+    pub fn get_controller_players(&self) -> Vec<u32> {
+        let mut players: Vec<u32> = self.controller_players.values().cloned().collect();
+        players.sort();
+        players
+    }
+
+    pub fn input_code_to_event(&self, code: InputCode, state: PressState) -> Option<InputEvent> {
+        let pressed = state == PressState::Pressed;
+        if let Some((player, button)) = Self::split_controller_button_code(&code) {
+            let which = self.controller_instance(player);
+            let b = InputEvent::controller_button_code(&button);
+            return Some(if pressed {
+                InputEvent::ControllerButtonDown { which: which, button: b }
+            } else {
+                InputEvent::ControllerButtonUp { which: which, button: b }
+            });
+        }
+        let (player, axis) = match code {
+            InputCode::ControllerLeftTrigger { player } => (player, Axis::TriggerLeft),
+            InputCode::ControllerRightTrigger { player } => (player, Axis::TriggerRight),
+            _ => { return InputEvent::from_input_code(code, state); }
+        };
+        let which = self.controller_instance(player);
+        Some(InputEvent::ControllerAxisMotion {
+            which: which,
+            axis: InputEvent::controller_axis_code(&axis),
+            value: if pressed { i16::max_value() } else { 0 }
+        })
+    }
+
+    fn captured_controller_code(&self, event: &InputEvent) -> Option<InputCode> {
+        match event {
+            InputEvent::ControllerButtonDown { which, button } => {
+                let player = self.controller_players.get(which).cloned().unwrap_or(0);
+                Some(Self::join_controller_button_code(player, InputEvent::controller_button(*button)))
+            },
+            InputEvent::ControllerAxisMotion { which, axis, value } => {
+                if *value < CONTROLLER_TRIGGER_THRESHOLD { return None; }
+                let player = self.controller_players.get(which).cloned().unwrap_or(0);
+                match InputEvent::controller_axis(*axis) {
+                    Axis::TriggerLeft => Some(InputCode::ControllerLeftTrigger { player: player }),
+                    Axis::TriggerRight => Some(InputCode::ControllerRightTrigger { player: player }),
+                    _ => None
+                }
+            },
+            _ => { None }
+        }
+    }
+
+    fn split_controller_button_code(code: &InputCode) -> Option<(u32, Button)> {
+        match *code {
+            InputCode::ControllerA { player } => Some((player, Button::A)),
+            InputCode::ControllerB { player } => Some((player, Button::B)),
+            InputCode::ControllerX { player } => Some((player, Button::X)),
+            InputCode::ControllerY { player } => Some((player, Button::Y)),
+            InputCode::ControllerBack { player } => Some((player, Button::Back)),
+            InputCode::ControllerGuide { player } => Some((player, Button::Guide)),
+            InputCode::ControllerStart { player } => Some((player, Button::Start)),
+            InputCode::ControllerLeftStickButton { player } => Some((player, Button::LeftStick)),
+            InputCode::ControllerRightStickButton { player } => Some((player, Button::RightStick)),
+            InputCode::ControllerLeftShoulder { player } => Some((player, Button::LeftShoulder)),
+            InputCode::ControllerRightShoulder { player } => Some((player, Button::RightShoulder)),
+            InputCode::ControllerDPadUp { player } => Some((player, Button::DPadUp)),
+            InputCode::ControllerDPadDown { player } => Some((player, Button::DPadDown)),
+            InputCode::ControllerDPadLeft { player } => Some((player, Button::DPadLeft)),
+            InputCode::ControllerDPadRight { player } => Some((player, Button::DPadRight)),
+            _ => None
+        }
+    }
+
+    fn join_controller_button_code(player: u32, button: Button) -> InputCode {
+        match button {
+            Button::A => InputCode::ControllerA { player: player },
+            Button::B => InputCode::ControllerB { player: player },
+            Button::X => InputCode::ControllerX { player: player },
+            Button::Y => InputCode::ControllerY { player: player },
+            Button::Back => InputCode::ControllerBack { player: player },
+            Button::Guide => InputCode::ControllerGuide { player: player },
+            Button::Start => InputCode::ControllerStart { player: player },
+            Button::LeftStick => InputCode::ControllerLeftStickButton { player: player },
+            Button::RightStick => InputCode::ControllerRightStickButton { player: player },
+            Button::LeftShoulder => InputCode::ControllerLeftShoulder { player: player },
+            Button::RightShoulder => InputCode::ControllerRightShoulder { player: player },
+            Button::DPadUp => InputCode::ControllerDPadUp { player: player },
+            Button::DPadDown => InputCode::ControllerDPadDown { player: player },
+            Button::DPadLeft => InputCode::ControllerDPadLeft { player: player },
+            Button::DPadRight => InputCode::ControllerDPadRight { player: player }
+        }
+    }
+
+    pub fn push_events(&mut self, mut events: Vec<InputEvent>) {
+        self.pending_events.append(&mut events);
+    }
+
+    pub fn take_pending_events(&mut self) -> Vec<InputEvent> {
+        std::mem::replace(&mut self.pending_events, Vec::new())
+    }
+
+    pub fn inject_event(&mut self, event: InputEvent) {
+        self.injected_events.push(event);
+    }
+
+    pub fn take_injected_events(&mut self) -> Vec<InputEvent> {
+        std::mem::replace(&mut self.injected_events, Vec::new())
+    }
+
+    pub fn start_recording(&mut self, recording: InputRecording) {
+        self.recording = Some(recording);
+    }
+
+    pub fn stop_recording(&mut self) -> Option<InputRecording> {
+        self.recording.take()
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    pub fn advance_update_time(&mut self, now: i64) -> i64 {
+        let elapsed = now - self.update_time;
+        self.update_time = now;
+        elapsed
+    }
+
+    pub fn record_frame(&mut self, delta: i64, elapsed: i64, events: &Vec<InputEvent>) {
+        if let Some(recording) = &mut self.recording {
+            recording.push_frame(delta, elapsed, events.clone());
+        }
+    }
+
+    pub fn start_replay(&mut self, recording: InputRecording) {
+        self.replay = Some(recording);
+        self.replay_frame = 0;
+    }
+
+    pub fn stop_replay(&mut self) {
+        self.replay = None;
+        self.replay_frame = 0;
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        self.replay.is_some()
+    }
+
+    pub fn next_replay_frame(&mut self) -> Option<InputFrame> {
+        let frame = self.replay.as_ref().and_then(|r| r.frames.get(self.replay_frame).cloned());
+        self.replay_frame += 1;
+        frame
+    }
+
     pub fn get_mouse_pointer(&self) -> Point {
         self.state.mouse_pointer.clone()
     }
 
-    fn get_mouse_button_state(&self, button: &MouseButton) -> (bool, bool, bool) {
+    pub fn get_mouse_button_state(&self, button: &MouseButton) -> (bool, bool, bool) {
         let prev_state = self.prev_state.mouses.get(button).cloned().unwrap_or(false);
         let state = self.state.mouses.get(button).cloned().unwrap_or(false);
         (!prev_state && state, state, prev_state && !state)
     }
 
-    fn get_mouse_wheel_state(&self, direction: MouseWheelDirection) -> (bool, bool, bool) {
-        let prev_state = self.prev_state.mousewheels.get(&direction).cloned().unwrap_or(false);
-        let state = self.state.mousewheels.get(&direction).cloned().unwrap_or(false);
+    pub fn get_mouse_motion(&self) -> Point {
+        self.state.mouse_motion.clone()
+    }
+
+    pub fn get_wheel_delta(&self) -> Point {
+        self.state.wheel_delta.clone()
+    }
+
+    fn is_wheel_direction(delta: &Point, direction: StickDirection) -> bool {
+        match direction {
+            StickDirection::Up => delta.y() > 0,
+            StickDirection::Down => delta.y() < 0,
+            StickDirection::Left => delta.x() < 0,
+            StickDirection::Right => delta.x() > 0
+        }
+    }
+
+    fn get_mouse_wheel_state(&self, direction: StickDirection) -> (bool, bool, bool) {
+        let prev_state = Self::is_wheel_direction(&self.prev_state.wheel_delta, direction);
+        let state = Self::is_wheel_direction(&self.state.wheel_delta, direction);
         (!prev_state && state, state, prev_state && !state)
     }
 
+    fn update_mouse_wheel_info(&self, info: &mut InputInfo, direction: StickDirection) {
+        let status = self.get_mouse_wheel_state(direction);
+        info.update_press_state(status.clone());
+        if status.0 || status.1 || status.2 {
+            info.mouse_position = self.get_mouse_pointer();
+            info.wheel_delta = self.get_wheel_delta();
+        }
+    }
+
+    fn is_text_key(key: &Keycode) -> bool {
+        let code = *key as i32;
+        if code >= Keycode::Space as i32 && code <= Keycode::Delete as i32 { return true; }
+        match *key {
+            Keycode::Backspace |
+            Keycode::Kp0 | Keycode::Kp1 | Keycode::Kp2 | Keycode::Kp3 | Keycode::Kp4 |
+            Keycode::Kp5 | Keycode::Kp6 | Keycode::Kp7 | Keycode::Kp8 | Keycode::Kp9 |
+            Keycode::KpPeriod | Keycode::KpDivide | Keycode::KpMultiply | Keycode::KpMinus | Keycode::KpPlus => true,
+            _ => false
+        }
+    }
+
     fn get_keyboard_state(&self, key: &Keycode) -> (bool, bool, bool) {
         let prev_state = self.prev_state.keys.get(key).cloned().unwrap_or(false);
         let state = self.state.keys.get(key).cloned().unwrap_or(false);
@@ -258,11 +951,104 @@ impl InputDirector {
         (!p && s, s, p && !s)
     }
 
+    fn get_controller_button_state(&self, player: u32, button: Button) -> (bool, bool, bool) {
+        let p = self.prev_state.controllers.get(&player).map(|e| e.buttons.get(&button).cloned().unwrap_or(false)).unwrap_or(false);
+        let s = self.state.controllers.get(&player).map(|e| e.buttons.get(&button).cloned().unwrap_or(false)).unwrap_or(false);
+        (!p && s, s, p && !s)
+    }
+
+    fn get_controller_axis(state: &InputState, player: u32, axis: Axis) -> i16 {
+        state.controllers.get(&player).map(|e| e.axes.get(&axis).cloned().unwrap_or(0)).unwrap_or(0)
+    }
+
+    fn get_controller_trigger_state(&self, player: u32, axis: Axis) -> (bool, bool, bool) {
+        let p = Self::get_controller_axis(&self.prev_state, player, axis) >= CONTROLLER_TRIGGER_THRESHOLD;
+        let s = Self::get_controller_axis(&self.state, player, axis) >= CONTROLLER_TRIGGER_THRESHOLD;
+        (!p && s, s, p && !s)
+    }
+
+    fn get_controller_stick(&self, player: u32, x: Axis, y: Axis) -> Point {
+        Point::new(
+            Self::get_controller_axis(&self.state, player, x) as i32,
+            Self::get_controller_axis(&self.state, player, y) as i32
+        )
+    }
+
     fn get_joystick_axis(&self, index: i32) -> Point {
-        if let Some(s) = self.state.joystikcs.get(&index) {
-            return s.sticks.clone();
+        Point::new(
+            Self::get_joystick_raw_axis(&self.state, index, 0) as i32,
+            Self::get_joystick_raw_axis(&self.state, index, 1) as i32
+        )
+    }
+
+    fn get_joystick_raw_axis(state: &InputState, index: i32, axis: u8) -> i16 {
+        state.joystikcs.get(&index).map(|e| e.axes.get(&axis).cloned().unwrap_or(0)).unwrap_or(0)
+    }
+
+    fn get_joystick_stick_value(&self, state: &InputState, index: i32, stick: u8) -> (f32, f32) {
+        self.normalize_stick(
+            Self::get_joystick_raw_axis(state, index, stick * 2),
+            Self::get_joystick_raw_axis(state, index, stick * 2 + 1)
+        )
+    }
+
+    fn get_joystick_trigger_value(&self, index: i32, axis: u8) -> f32 {
+        let v = Self::normalize_axis(Self::get_joystick_raw_axis(&self.state, index, axis));
+        if v < self.stick_deadzone { 0.0 } else { v }
+    }
+
+    fn get_joystick_trigger_state(&self, index: i32, axis: u8) -> (bool, bool, bool) {
+        let p = Self::get_joystick_raw_axis(&self.prev_state, index, axis) >= CONTROLLER_TRIGGER_THRESHOLD;
+        let s = Self::get_joystick_raw_axis(&self.state, index, axis) >= CONTROLLER_TRIGGER_THRESHOLD;
+        (!p && s, s, p && !s)
+    }
+
+    fn get_controller_stick_value(&self, state: &InputState, player: u32, x: Axis, y: Axis) -> (f32, f32) {
+        self.normalize_stick(
+            Self::get_controller_axis(state, player, x),
+            Self::get_controller_axis(state, player, y)
+        )
+    }
+
+    fn get_controller_trigger_value(&self, player: u32, axis: Axis) -> f32 {
+        let v = Self::normalize_axis(Self::get_controller_axis(&self.state, player, axis));
+        if v < self.stick_deadzone { 0.0 } else { v }
+    }
+
+    fn normalize_axis(value: i16) -> f32 {
+        (value as f32 / i16::max_value() as f32).max(-1.0).min(1.0)
+    }
+
+    fn normalize_stick(&self, x: i16, y: i16) -> (f32, f32) {
+        let (nx, ny) = (Self::normalize_axis(x), Self::normalize_axis(y));
+        let magnitude = nx.hypot(ny);
+        if magnitude <= self.stick_deadzone || magnitude == 0.0 { return (0.0, 0.0); }
+        let scaled = ((magnitude - self.stick_deadzone) / (1.0 - self.stick_deadzone)).min(1.0);
+        (nx / magnitude * scaled, ny / magnitude * scaled)
+    }
+
+    fn is_stick_direction(value: (f32, f32), direction: StickDirection) -> bool {
+        match direction {
+            StickDirection::Up => value.1 <= -STICK_DIRECTION_THRESHOLD,
+            StickDirection::Down => value.1 >= STICK_DIRECTION_THRESHOLD,
+            StickDirection::Left => value.0 <= -STICK_DIRECTION_THRESHOLD,
+            StickDirection::Right => value.0 >= STICK_DIRECTION_THRESHOLD
         }
-        JoystickState::new().sticks.clone()
+    }
+
+    fn get_stick_direction_state(prev_value: (f32, f32), value: (f32, f32), direction: StickDirection) -> (bool, bool, bool) {
+        let p = Self::is_stick_direction(prev_value, direction);
+        let s = Self::is_stick_direction(value, direction);
+        (!p && s, s, p && !s)
+    }
+
+    pub fn get_stick_deadzone(&self) -> f32 {
+        self.stick_deadzone
+    }
+
+    pub fn set_stick_deadzone(&mut self, deadzone: f32) {
+        if deadzone < 0.0 || deadzone >= 1.0 { panic!(format!("invalid stick deadzone: {}", deadzone)); }
+        self.stick_deadzone = deadzone;
     }
 
     pub fn is_quit(&self) -> bool {
@@ -273,4 +1059,88 @@ impl InputDirector {
         self.quit = false;
     }
 
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn key_down(keycode: Keycode) -> InputEvent {
+        InputEvent::KeyDown { keycode: keycode as i32 }
+    }
+
+    #[test]
+    fn stick_inside_the_deadzone_is_zero() {
+        let mut input = InputDirector::new();
+        input.set_stick_deadzone(0.25);
+        let small = (i16::max_value() as f32 * 0.15) as i16;
+        assert_eq!(input.normalize_stick(small, small), (0.0, 0.0));
+        assert_eq!(input.normalize_stick(0, 0), (0.0, 0.0));
+    }
+
+    #[test]
+    fn stick_deadzone_is_radial_and_rescaled() {
+        let mut input = InputDirector::new();
+        input.set_stick_deadzone(0.2);
+        let (x, y) = input.normalize_stick(i16::max_value(), 0);
+        assert!((x - 1.0).abs() < 1e-4 && y == 0.0);
+        let (x, y) = input.normalize_stick(0, i16::max_value() / 2);
+        assert!(x == 0.0 && (y - 0.375).abs() < 1e-3);
+        let (x, y) = input.normalize_stick(i16::max_value(), i16::max_value());
+        assert!((x.hypot(y) - 1.0).abs() < 1e-4);
+        assert!((x - y).abs() < 1e-6);
+        let (x, y) = input.normalize_stick(i16::min_value(), 0);
+        assert!((x + 1.0).abs() < 1e-4 && y == 0.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn stick_deadzone_must_be_below_one() {
+        InputDirector::new().set_stick_deadzone(1.0);
+    }
+
+    #[test]
+    fn text_input_mutes_only_text_keys() {
+        let mut input = InputDirector::new();
+        input.insert_key_code("confirm", InputCode::Return);
+        input.insert_key_code("cancel", InputCode::Escape);
+        input.insert_key_code("left", InputCode::Left);
+        input.insert_key_code("jump", InputCode::A);
+        input.start_text_input();
+        input.update_state(vec![key_down(Keycode::Return), key_down(Keycode::Escape), key_down(Keycode::Left), key_down(Keycode::A)]);
+        assert!(input.get_input_info(None, "confirm").is_press_start());
+        assert!(input.get_input_info(None, "cancel").is_press_start());
+        assert!(input.get_input_info(None, "left").is_press_start());
+        assert!(!input.get_input_info(None, "jump").is_press_start());
+    }
+
+    #[test]
+    fn backspace_is_ignored_while_composing() {
+        let mut input = InputDirector::new();
+        input.start_text_input();
+        input.update_state(vec![
+            InputEvent::TextEditing { text: "か".to_owned(), start: 1, length: 0 },
+            key_down(Keycode::Backspace)
+        ]);
+        assert_eq!(input.take_text_input().get_backspaces(), 0);
+        input.update_state(vec![
+            InputEvent::TextEditing { text: String::new(), start: 0, length: 0 },
+            key_down(Keycode::Backspace)
+        ]);
+        assert_eq!(input.take_text_input().get_backspaces(), 1);
+    }
+
+    #[test]
+    fn controller_codes_can_be_injected_without_a_pad() {
+        let mut input = InputDirector::new();
+        input.insert_key_code("confirm", InputCode::ControllerA { player: 1 });
+        let down = input.input_code_to_event(InputCode::ControllerA { player: 1 }, PressState::Pressed).unwrap();
+        input.update_state(vec![down]);
+        assert_eq!(input.get_controller_players(), vec![1]);
+        assert!(input.get_input_info(None, "confirm").is_press_start());
+        let up = input.input_code_to_event(InputCode::ControllerA { player: 1 }, PressState::Released).unwrap();
+        input.update_state(vec![up]);
+        assert!(input.get_input_info(None, "confirm").is_pressed());
+    }
+
+}
@@ -5,13 +5,16 @@ mod render;
 mod input;
 mod sound;
 mod variable;
+mod clock;
+mod pointer;
+mod config;
 
 use std::cell::RefCell;
 use std::rc::Rc;
 use std::any::Any;
-use ::application::{ Application };
-use ::util::{ SaveMigrator };
-use ::util::parameter::{ Size, InputCode, InputInfo, Point, MusicOption };
+use ::application::{ Application, WindowMode };
+use ::util::{ SaveMigrator, RandSnapshot, with_context, output_warning_log };
+use ::util::parameter::{ Size, InputCode, InputCodeMap, InputInfo, InputEvent, InputRecording, PressState, Point, Rect, TextInputInfo, PointerEvent, TouchPoint, TouchGesture, MusicOption, SEOption, SoundBus };
 use ::node::{ Node, NodeLike, NodeDelegate, NodeId };
 use ::node::scene::{ SceneLike };
 use ::node::scene::transition::{ SceneTransition, TransitionStatus };
@@ -24,12 +27,20 @@ use self::input::InputDirector;
 use self::sound::SoundDirector;
 use self::variable::VariableDirector;
 use self::resource::ResourceDirector;
+use self::clock::ClockDirector;
+use self::pointer::PointerDirector;
+use self::config::ConfigDirector;
+
+pub use self::config::{ CONFIG_WINDOW_MODE, CONFIG_INPUT_CODE_MAP, CONFIG_VOLUME_MASTER, CONFIG_VOLUME_BGM, CONFIG_VOLUME_SE, CONFIG_VOLUME_VOICE };
 use sdl2::{ EventPump };
+use sdl2::event::{ Event, WindowEvent };
+use sdl2::mouse::{ MouseButton };
 use sdl2::pixels::{ Color };
 use serde::de::DeserializeOwned;
 use serde::ser::Serialize;
 use serde_json::{ Value };
 use rand::distributions::{ Standard, Distribution };
+use image::{ RgbaImage };
 
 pub struct Director<'a> {
     application: RefCell<ApplicationDirector>,
@@ -38,7 +49,10 @@ pub struct Director<'a> {
     input: RefCell<InputDirector>,
     sound: RefCell<SoundDirector<'a>>,
     variable: RefCell<VariableDirector>,
-    resource: RefCell<ResourceDirector<'a>>
+    resource: RefCell<ResourceDirector<'a>>,
+    clock: RefCell<ClockDirector>,
+    pointer: RefCell<PointerDirector>,
+    config: RefCell<ConfigDirector>
 }
 
 impl <'a> Director<'a> {
@@ -51,12 +65,121 @@ impl <'a> Director<'a> {
             input: RefCell::new(InputDirector::new()),
             sound: RefCell::new(SoundDirector::new()),
             variable: RefCell::new(VariableDirector::new()),
-            resource: RefCell::new(ResourceDirector::new())
+            resource: RefCell::new(ResourceDirector::new()),
+            clock: RefCell::new(ClockDirector::new()),
+            pointer: RefCell::new(PointerDirector::new()),
+            config: RefCell::new(ConfigDirector::new())
         }
     }
 
+    pub fn reset(&self) {
+        self.application.replace(ApplicationDirector::new());
+        self.node.replace(NodeDirector::new());
+        self.render.replace(RenderDirector::new());
+        self.input.replace(InputDirector::new());
+        self.sound.replace(SoundDirector::new());
+        self.variable.replace(VariableDirector::new());
+        self.resource.replace(ResourceDirector::new());
+        self.clock.replace(ClockDirector::new());
+        self.pointer.replace(PointerDirector::new());
+        self.config.replace(ConfigDirector::new());
+    }
+
     pub fn window_size(&self) -> Size {
-        self.application.borrow().window_size()
+        self.render.borrow().get_window_size()
+    }
+
+    pub fn get_window_mode(&self) -> WindowMode {
+        with_context(|c| c.get_window_mode())
+    }
+
+    pub fn set_window_mode(&self, window_mode: WindowMode) -> Result<(), String> {
+        self.apply_window_mode(window_mode)?;
+        if let Err(e) = self.config.borrow_mut().set(CONFIG_WINDOW_MODE, &window_mode) {
+            output_warning_log(&format!("failed to save window mode: {}", e));
+        }
+        Ok(())
+    }
+
+    fn apply_window_mode(&self, window_mode: WindowMode) -> Result<(), String> {
+        with_context(|c| c.set_window_mode(window_mode))?;
+        self.render.borrow_mut().set_window_size(with_context(|c| c.get_window_size()));
+        Ok(())
+    }
+
+    pub fn set_window_size(&self, size: Size) -> Result<(), String> {
+        with_context(|c| c.set_window_size(size))?;
+        self.render.borrow_mut().set_window_size(with_context(|c| c.get_window_size()));
+        Ok(())
+    }
+
+    fn handle_window_event(&self, event: &Event) {
+        let win_event = match event {
+            Event::Window { win_event, .. } => win_event,
+            _ => { return; }
+        };
+        match win_event {
+            WindowEvent::SizeChanged(width, height) => {
+                self.render.borrow_mut().set_window_size(Size::new(*width as u32, *height as u32));
+            },
+            WindowEvent::FocusLost | WindowEvent::Minimized => {
+                self.enter_background();
+            },
+            WindowEvent::FocusGained | WindowEvent::Restored => {
+                self.enter_foreground();
+            },
+            _ => {}
+        }
+    }
+
+    fn enter_background(&self) {
+        if self.application.borrow().is_background() { return; }
+        self.application.borrow_mut().set_background(true);
+        let application = self.application.borrow().application();
+        application.application_did_enter_background();
+        if application.pause_on_focus_loss() { self.set_suspended(true); }
+    }
+
+    fn enter_foreground(&self) {
+        if !self.application.borrow().is_background() { return; }
+        self.application.borrow_mut().set_background(false);
+        let application = self.application.borrow().application();
+        application.application_will_enter_foreground();
+        if application.pause_on_focus_loss() { self.set_suspended(false); }
+    }
+
+    pub fn is_background(&self) -> bool {
+        self.application.borrow().is_background()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.clock.borrow().is_paused()
+    }
+
+    pub fn set_paused(&self, paused: bool) {
+        let was_paused = self.is_paused();
+        self.clock.borrow_mut().set_paused(paused);
+        self.apply_paused(was_paused);
+    }
+
+    pub fn is_suspended(&self) -> bool {
+        self.clock.borrow().is_suspended()
+    }
+
+    fn set_suspended(&self, suspended: bool) {
+        let was_paused = self.is_paused();
+        self.clock.borrow_mut().set_suspended(suspended);
+        self.apply_paused(was_paused);
+    }
+
+    fn apply_paused(&self, was_paused: bool) {
+        let paused = self.is_paused();
+        if paused == was_paused { return; }
+        if paused {
+            self.sound.borrow_mut().pause_all();
+        } else {
+            self.sound.borrow_mut().resume_all();
+        }
     }
 
     pub fn get_resolution_size(&self) -> Size {
@@ -64,7 +187,35 @@ impl <'a> Director<'a> {
     }
 
     pub fn rand<T>(&self) -> T where Standard: Distribution<T> {
-        self.application.borrow_mut().rand()
+        self.application.borrow_mut().rand("")
+    }
+
+    pub fn rand_with<T, A>(&self, stream: A) -> T
+    where Standard: Distribution<T>, A: Into<String>
+    {
+        let s = stream.into();
+        self.application.borrow_mut().rand(&s)
+    }
+
+    pub fn seed_rand(&self, seed: u64) {
+        self.application.borrow_mut().seed_rand(seed);
+    }
+
+    pub fn seed_rand_stream<A>(&self, stream: A, seed: u64) where A: Into<String> {
+        let s = stream.into();
+        self.application.borrow_mut().seed_rand_stream(&s, seed);
+    }
+
+    pub fn get_rand_seed(&self) -> u64 {
+        self.application.borrow().get_rand_seed()
+    }
+
+    pub fn snapshot_rand(&self) -> RandSnapshot {
+        self.application.borrow().snapshot_rand()
+    }
+
+    pub fn restore_rand(&self, snapshot: &RandSnapshot) {
+        self.application.borrow_mut().restore_rand(snapshot);
     }
 
     pub fn is_continuing(&self) -> bool {
@@ -90,18 +241,21 @@ impl <'a> Director<'a> {
     pub fn replace_scene<T>(&self, scene: Rc<dyn SceneLike>, transition: T)
     where T: Into<Rc<SceneTransition>>
     {
+        self.flush_config();
         self.application.borrow_mut().replace_scene(scene, transition.into());
     }
 
     pub fn push_scene<T>(&self, scene: Rc<dyn SceneLike>, transition: T)
         where T: Into<Rc<SceneTransition>>
     {
+        self.flush_config();
         self.application.borrow_mut().push_scene(scene, transition.into());
     }
 
     pub fn pop_scene<T>(&self, transition: T)
         where T: Into<Rc<SceneTransition>>
     {
+        self.flush_config();
         self.application.borrow_mut().pop_scene(transition.into());
     }
 
@@ -113,12 +267,105 @@ impl <'a> Director<'a> {
         self.application.borrow_mut().set_application(application.clone());
         self.render.borrow_mut().set_application(application.clone());
         self.variable.borrow_mut().set_application(application.clone());
+        self.config.borrow_mut().set_application(application.clone());
+    }
+
+    fn apply_volume_config(&self, key: &str, bus: SoundBus) {
+        let volume = self.get_config::<f32>(key).unwrap_or(1.0);
+        self.sound.borrow_mut().set_volume(bus, volume);
+    }
+
+    fn volume_config_key(bus: SoundBus) -> &'static str {
+        match bus {
+            SoundBus::Master => CONFIG_VOLUME_MASTER,
+            SoundBus::BGM => CONFIG_VOLUME_BGM,
+            SoundBus::SE => CONFIG_VOLUME_SE,
+            SoundBus::Voice => CONFIG_VOLUME_VOICE
+        }
+    }
+
+    pub fn get_config<T>(&self, key: &str) -> Option<T> where T: DeserializeOwned {
+        self.config.borrow().get(key)
+    }
+
+    pub fn set_config<T>(&self, key: &str, value: &T) -> Result<(), String> where T: Serialize {
+        let changed = self.config.borrow_mut().set(key, value)?;
+        if changed { self.apply_config(key); }
+        Ok(())
+    }
+
+    pub fn flush_config(&self) {
+        if let Err(e) = self.config.borrow_mut().flush() {
+            output_warning_log(&format!("failed to save config: {}", e));
+        }
+    }
+
+    pub fn reset_config(&self, key: &str) -> Result<(), String> {
+        self.config.borrow_mut().reset(key)?;
+        self.apply_config(key);
+        Ok(())
+    }
+
+    pub fn apply_all_config(&self) {
+        for key in &[CONFIG_WINDOW_MODE, CONFIG_INPUT_CODE_MAP, CONFIG_VOLUME_MASTER, CONFIG_VOLUME_BGM, CONFIG_VOLUME_SE, CONFIG_VOLUME_VOICE] {
+            self.apply_config(key);
+        }
+    }
+
+    fn apply_config(&self, key: &str) {
+        match key {
+            CONFIG_WINDOW_MODE => {
+                if let Some(window_mode) = self.get_config::<WindowMode>(key) {
+                    if let Err(e) = self.apply_window_mode(window_mode) {
+                        output_warning_log(&format!("failed to apply window mode: {}", e));
+                        let default_mode = self.application.borrow().application().window_mode();
+                        if let Err(e) = self.apply_window_mode(default_mode) {
+                            output_warning_log(&format!("failed to apply default window mode: {}", e));
+                        }
+                    }
+                }
+            },
+            CONFIG_INPUT_CODE_MAP => {
+                if let Some(input_code_map) = self.get_config::<InputCodeMap>(key) {
+                    self.set_input_code_map(input_code_map);
+                }
+            },
+            CONFIG_VOLUME_MASTER => { self.apply_volume_config(key, SoundBus::Master); },
+            CONFIG_VOLUME_BGM => { self.apply_volume_config(key, SoundBus::BGM); },
+            CONFIG_VOLUME_SE => { self.apply_volume_config(key, SoundBus::SE); },
+            CONFIG_VOLUME_VOICE => { self.apply_volume_config(key, SoundBus::Voice); },
+            _ => {}
+        }
     }
 
     pub fn set_current_fps(&self, fps: usize) {
         self.application.borrow_mut().set_current_fps(fps);
     }
 
+    pub fn get_clock_time(&self) -> i64 {
+        self.clock.borrow().now()
+    }
+
+    pub fn get_frame_delta(&self) -> i64 {
+        self.clock.borrow().delta()
+    }
+
+    pub fn tick_clock(&self, delta: i64) {
+        self.clock.borrow_mut().tick(delta);
+    }
+
+    pub fn advance_clock(&self, delta: i64) {
+        self.clock.borrow_mut().advance(delta);
+    }
+
+    pub fn is_manual_clock(&self) -> bool {
+        self.clock.borrow().is_manual()
+    }
+
+    pub fn set_manual_clock(&self, manual: bool) {
+        self.clock.borrow_mut().set_manual(manual);
+    }
+
     pub fn default_label_option(&self) -> Option<LabelOption> {
         self.application.borrow().default_label_option()
     }
@@ -216,6 +463,14 @@ impl <'a> Director<'a> {
         self.render.borrow_mut().render_canvas(scene, prev_scene, transition)
     }
 
+    pub fn capture_screen(&self) -> Option<RgbaImage> {
+        self.render.borrow().capture_screen()
+    }
+
+    pub fn capture_node(&self, node: Rc<dyn NodeLike>) -> Option<RgbaImage> {
+        self.render.borrow_mut().capture_node(node)
+    }
+
     pub fn destroy_render_cache(&self, key: &ResourceKey) {
         self.render.borrow_mut().destroy_render_cache(key);
     }
@@ -225,17 +480,250 @@ impl <'a> Director<'a> {
         self.render.borrow().convert_window_point_to_resolution_point(&p)
     }
 
+    pub fn get_mouse_motion(&self) -> Point {
+        let motion = self.input.borrow().get_mouse_motion();
+        self.render.borrow().convert_window_delta_to_resolution_delta(&motion)
+    }
+
+    pub fn get_mouse_wheel_delta(&self) -> Point {
+        self.input.borrow().get_wheel_delta()
+    }
+
     pub fn get_input_info<A>(&self, key: A) -> InputInfo
     where A: Into<String>
     {
-        let mut info = self.input.borrow().get_input_info(key);
-        let p = self.render.borrow().convert_window_point_to_resolution_point(&info.mouse_position);
-        info.mouse_position = p;
+        self.get_input_info_in(None, key)
+    }
+
+    pub fn get_input_info_in<A>(&self, context: Option<&str>, key: A) -> InputInfo
+    where A: Into<String>
+    {
+        let mut info = self.input.borrow().get_input_info(context, key);
+        let render = self.render.borrow();
+        info.mouse_position = render.convert_window_point_to_resolution_point(&info.mouse_position);
+        info.mouse_motion = render.convert_window_delta_to_resolution_delta(&info.mouse_motion);
         info
     }
 
-    pub fn update_input_state(&self, event_pump: &mut EventPump) {
-        self.input.borrow_mut().update_state(event_pump)
+    pub fn poll_input(&self, event_pump: &mut EventPump) {
+        let sdl_events: Vec<Event> = event_pump.poll_iter().collect();
+        for event in &sdl_events {
+            self.handle_window_event(event);
+        }
+        let real_events: Vec<InputEvent> = sdl_events.iter().filter_map(|e| Self::convert_sdl_event(e)).collect();
+        self.input.borrow_mut().push_events(real_events);
+    }
+
+    pub fn update_input_state(&self) {
+        let mut input = self.input.borrow_mut();
+        let real_events = input.take_pending_events();
+        let mut events = if input.is_replaying() {
+            if let Some(frame) = input.next_replay_frame() {
+                self.clock.borrow_mut().replay(frame.elapsed, frame.delta);
+                let mut events = frame.events;
+                if real_events.contains(&InputEvent::Quit) { events.push(InputEvent::Quit); }
+                events
+            } else {
+                input.stop_replay();
+                self.clock.borrow_mut().set_manual(false);
+                real_events
+            }
+        } else {
+            real_events
+        };
+        events.append(&mut input.take_injected_events());
+        let elapsed = input.advance_update_time(self.clock.borrow().now());
+        input.record_frame(self.clock.borrow().delta(), elapsed, &events);
+        input.set_window_size(self.window_size());
+        input.update_state(events);
+        input.update_key_trackers(elapsed);
+        input.update_touches(elapsed);
+    }
+
+    pub fn get_touches(&self) -> Vec<TouchPoint> {
+        let render = self.render.borrow();
+        self.input.borrow().get_touches().into_iter().map(|t| {
+            TouchPoint {
+                position: render.convert_window_point_to_resolution_point(&t.position),
+                start_position: render.convert_window_point_to_resolution_point(&t.start_position),
+                ..t
+            }
+        }).collect()
+    }
+
+    pub fn get_touch_gestures(&self) -> Vec<TouchGesture> {
+        let render = self.render.borrow();
+        self.input.borrow().get_touch_gestures().into_iter().map(|g| {
+            match g {
+                TouchGesture::Tap { position } => {
+                    TouchGesture::Tap { position: render.convert_window_point_to_resolution_point(&position) }
+                },
+                TouchGesture::LongPress { position } => {
+                    TouchGesture::LongPress { position: render.convert_window_point_to_resolution_point(&position) }
+                },
+                TouchGesture::Swipe { start, end, direction } => {
+                    TouchGesture::Swipe {
+                        start: render.convert_window_point_to_resolution_point(&start),
+                        end: render.convert_window_point_to_resolution_point(&end),
+                        direction: direction
+                    }
+                },
+                TouchGesture::Pinch { center, scale, rotation } => {
+                    TouchGesture::Pinch { center: render.convert_window_point_to_resolution_point(&center), scale: scale, rotation: rotation }
+                }
+            }
+        }).collect()
+    }
+
+    pub fn dispatch_pointer_events(&self) {
+        let position = self.get_mouse_position();
+        let scene = self.get_nodelike(&self.get_scene().inner_id());
+        let path: Vec<NodeId> = PointerDirector::hit_test(scene, &position).iter().map(|n| n.inner_id()).collect();
+        let press_state = self.input.borrow().get_mouse_button_state(&MouseButton::Left);
+        let dispatches = self.pointer.borrow_mut().update(position.clone(), path, press_state);
+        for dispatch in dispatches {
+            let nodes: Vec<Rc<dyn NodeLike>> = dispatch.path.iter().rev().filter_map(|id| self.node.borrow().find_nodelike(id)).collect();
+            let target = match nodes.first() {
+                Some(n) => n.inner_id(),
+                None => { continue; }
+            };
+            let event = PointerEvent::new(dispatch.event_type, target, position.clone(), dispatch.start_position.clone(), dispatch.drag_source.clone());
+            for node in nodes {
+                event.local_position.replace(PointerDirector::convert_to_local_point(node.clone(), &position));
+                node.inner_dispatch_pointer_event(&event);
+                if !dispatch.bubbles || event.is_propagation_stopped() { break; }
+            }
+        }
+    }
+
+    pub fn set_key_repeat(&self, delay: i64, interval: i64) {
+        self.input.borrow_mut().set_key_repeat(delay, interval);
+    }
+
+    pub fn is_input_buffered<A>(&self, context: Option<&str>, key: A, steps: u32) -> bool
+    where A: Into<String>
+    {
+        let k = key.into();
+        self.input.borrow().is_buffered(context, &k, steps)
+    }
+
+    pub fn push_input_context<A>(&self, name: A, pass_through: bool)
+    where A: Into<String>
+    {
+        let n = name.into();
+        self.input.borrow_mut().push_context(&n, pass_through);
+    }
+
+    pub fn pop_input_context<A>(&self, name: A)
+    where A: Into<String>
+    {
+        let n = name.into();
+        self.input.borrow_mut().pop_context(&n);
+    }
+
+    pub fn consume_input_buffer<A>(&self, key: A)
+    where A: Into<String>
+    {
+        let k = key.into();
+        self.input.borrow_mut().consume_buffered(&k);
+    }
+
+    pub fn start_text_input(&self, rect: Rect) {
+        let window_rect = self.render.borrow().convert_resolution_rect_to_window_rect(&rect);
+        with_context(|c| {
+            let text_input = c.canvas.window().subsystem().text_input();
+            text_input.set_rect(*window_rect);
+            text_input.start();
+        });
+        self.input.borrow_mut().start_text_input();
+    }
+
+    pub fn stop_text_input(&self) {
+        with_context(|c| c.canvas.window().subsystem().text_input().stop());
+        self.input.borrow_mut().stop_text_input();
+    }
+
+    pub fn is_text_input_active(&self) -> bool {
+        self.input.borrow().is_text_input_active()
+    }
+
+    pub fn take_text_input(&self) -> TextInputInfo {
+        self.input.borrow_mut().take_text_input()
+    }
+
+    fn convert_sdl_event(event: &Event) -> Option<InputEvent> {
+        match event {
+            Event::ControllerDeviceAdded { which, .. } => {
+                with_context(|c| c.open_game_controller(*which)).map(|id| InputEvent::ControllerDeviceAdded { which: id })
+            },
+            Event::ControllerDeviceRemoved { which, .. } => {
+                with_context(|c| c.close_game_controller(*which));
+                InputEvent::from_sdl_event(event)
+            },
+            _ => {
+                InputEvent::from_sdl_event(event)
+            }
+        }
+    }
+
+    pub fn inject_input(&self, code: InputCode, state: PressState) -> Result<(), String> {
+        let event = self.input.borrow().input_code_to_event(code, state);
+        match event {
+            Some(e) => {
+                self.input.borrow_mut().inject_event(e);
+                Ok(())
+            },
+            None => {
+                let c = serde_json::to_string(&code).map_err(|e| e.to_string())?;
+                Err(format!("input code cannot be injected: {}", c))
+            }
+        }
+    }
+
+    pub fn get_stick_deadzone(&self) -> f32 {
+        self.input.borrow().get_stick_deadzone()
+    }
+
+    pub fn set_stick_deadzone(&self, deadzone: f32) {
+        self.input.borrow_mut().set_stick_deadzone(deadzone);
+    }
+
+    pub fn get_controller_players(&self) -> Vec<u32> {
+        self.input.borrow().get_controller_players()
+    }
+
+    pub fn inject_mouse_motion(&self, point: Point) {
+        let prev = self.input.borrow().get_mouse_pointer();
+        let (xrel, yrel) = if prev.x() < 0 && prev.y() < 0 { (0, 0) } else { (point.x() - prev.x(), point.y() - prev.y()) };
+        self.input.borrow_mut().inject_event(InputEvent::MouseMotion { x: point.x(), y: point.y(), xrel: xrel, yrel: yrel });
+    }
+
+    pub fn start_input_recording(&self) {
+        let rand = self.application.borrow().snapshot_rand();
+        self.input.borrow_mut().start_recording(InputRecording::new(rand));
+    }
+
+    pub fn stop_input_recording(&self) -> Option<InputRecording> {
+        self.input.borrow_mut().stop_recording()
+    }
+
+    pub fn is_input_recording(&self) -> bool {
+        self.input.borrow().is_recording()
+    }
+
+    pub fn start_input_replay(&self, recording: InputRecording) {
+        self.application.borrow_mut().restore_rand(&recording.rand);
+        self.clock.borrow_mut().set_manual(true);
+        self.input.borrow_mut().start_replay(recording);
+    }
+
+    pub fn stop_input_replay(&self) {
+        self.input.borrow_mut().stop_replay();
+        self.clock.borrow_mut().set_manual(false);
+    }
+
+    pub fn is_input_replaying(&self) -> bool {
+        self.input.borrow().is_replaying()
     }
 
     pub fn add_key_code<A>(&self, key: A, code: InputCode)
@@ -258,6 +746,50 @@ impl <'a> Director<'a> {
         self.input.borrow_mut().reset_key_code(key);
     }
 
+    pub fn remove_key_code<A>(&self, key: A, code: &InputCode)
+    where A: Into<String>
+    {
+        self.input.borrow_mut().remove_key_code(key, code);
+    }
+
+    pub fn get_input_code_map(&self) -> InputCodeMap {
+        self.input.borrow().get_input_code_map()
+    }
+
+    pub fn set_input_code_map(&self, input_code_map: InputCodeMap) {
+        self.input.borrow_mut().set_input_code_map(input_code_map);
+    }
+
+    pub fn load_input_code_map<A>(&self, name: A) -> Result<(), String>
+    where A: Into<String>
+    {
+        let n = name.into();
+        self.input.borrow_mut().load_input_code_map(&n)
+    }
+
+    pub fn save_input_code_map<A>(&self, name: A) -> Result<(), String>
+    where A: Into<String>
+    {
+        let n = name.into();
+        self.input.borrow().save_input_code_map(&n)
+    }
+
+    pub fn start_input_capture(&self) {
+        self.input.borrow_mut().start_capture();
+    }
+
+    pub fn cancel_input_capture(&self) {
+        self.input.borrow_mut().cancel_capture();
+    }
+
+    pub fn is_input_capturing(&self) -> bool {
+        self.input.borrow().is_capturing()
+    }
+
+    pub fn take_captured_input(&self) -> Option<InputCode> {
+        self.input.borrow_mut().take_captured_input()
+    }
+
     pub fn is_quit(&self) -> bool {
         self.input.borrow().is_quit()
     }
@@ -266,6 +798,15 @@ impl <'a> Director<'a> {
         self.input.borrow_mut().reset_is_quit();
     }
 
+    pub fn initialize_audio(&self) {
+        let audio_mode = with_context(|c| c.audio_mode);
+        self.sound.borrow_mut().initialize(audio_mode);
+    }
+
+    pub fn take_audio_records(&self) -> Vec<String> {
+        self.sound.borrow_mut().take_audio_records()
+    }
+
     pub fn play_music<A, B>(&self, path: A, option: B)
     where A: Into<String>, B: Into<MusicOption>
     {
@@ -273,28 +814,117 @@ impl <'a> Director<'a> {
         self.sound.borrow_mut().play_music(&p, option.into());
     }
 
+    pub fn queue_music<A, B>(&self, path: A, option: B)
+    where A: Into<String>, B: Into<MusicOption>
+    {
+        let p = path.into();
+        self.sound.borrow_mut().queue_music(&p, option.into());
+    }
+
+    pub fn clear_music_queue(&self) {
+        self.sound.borrow_mut().clear_music_queue();
+    }
+
     pub fn stop_music(&self, fade_out: i32) {
-        self.sound.borrow().stop_music(fade_out);
+        self.sound.borrow_mut().stop_music(fade_out);
+    }
+
+    pub fn pause_music(&self) {
+        self.sound.borrow_mut().pause_music();
+    }
+
+    pub fn resume_music(&self) {
+        self.sound.borrow_mut().resume_music();
+    }
+
+    pub fn is_music_paused(&self) -> bool {
+        self.sound.borrow().is_music_paused()
+    }
+
+    pub fn get_current_music(&self) -> Option<String> {
+        self.sound.borrow().get_current_music()
+    }
+
+    pub fn get_music_position(&self) -> Option<f64> {
+        self.sound.borrow().get_music_position()
     }
 
     pub fn stop_se(&self, se: Rc<SE>) {
-        self.sound.borrow().stop_se(se);
+        self.sound.borrow_mut().stop_se(se);
     }
 
     pub fn stop_all_se(&self) {
-        self.sound.borrow().stop_all_se();
+        self.sound.borrow_mut().stop_all_se();
     }
 
-    pub fn play_se<A>(&self, path: A) -> Rc<SE>
-    where A: Into<String>
+    pub fn play_se<A, B>(&self, path: A, option: B) -> Rc<SE>
+    where A: Into<String>, B: Into<SEOption>
     {
         let p = path.into();
-        self.sound.borrow_mut().play_se(&p)
+        self.sound.borrow_mut().play_se(&p, option.into())
+    }
+
+    pub fn play_voice<A, B>(&self, path: A, option: B) -> Rc<SE>
+    where A: Into<String>, B: Into<SEOption>
+    {
+        let p = path.into();
+        self.sound.borrow_mut().play_voice(&p, option.into())
+    }
+
+    pub fn stop_voice(&self) {
+        self.sound.borrow_mut().stop_voice();
+    }
+
+    pub fn is_voice_playing(&self) -> bool {
+        self.sound.borrow().is_voice_playing()
+    }
+
+    pub fn get_voice_ducking(&self) -> f32 {
+        self.sound.borrow().get_voice_ducking()
+    }
+
+    pub fn set_voice_ducking(&self, ducking: f32) {
+        self.sound.borrow_mut().set_voice_ducking(ducking);
+    }
+
+    pub fn get_max_se_channels(&self) -> usize {
+        self.sound.borrow().get_max_se_channels()
+    }
+
+    pub fn set_max_se_channels(&self, channels: usize) {
+        self.sound.borrow_mut().set_max_se_channels(channels);
+    }
+
+    pub fn get_volume(&self, bus: SoundBus) -> f32 {
+        self.sound.borrow().get_volume(bus)
+    }
+
+    pub fn set_volume(&self, bus: SoundBus, volume: f32) {
+        self.sound.borrow_mut().set_volume(bus, volume);
+        let v = self.sound.borrow().get_volume(bus);
+        if let Err(e) = self.config.borrow_mut().set_deferred(Self::volume_config_key(bus), &v) {
+            output_warning_log(&format!("failed to save volume: {}", e));
+        }
+    }
+
+    pub fn get_music_volume(&self) -> f32 {
+        self.sound.borrow().get_music_volume()
+    }
+
+    pub fn set_music_volume(&self, volume: f32) {
+        self.sound.borrow_mut().set_music_volume(volume);
     }
 
     pub fn clean_se(&self) {
-        let seed: usize = self.rand();
-        self.sound.borrow_mut().clean_se(seed);
+        self.sound.borrow_mut().clean_se();
+    }
+
+    pub fn update_music(&self) {
+        self.sound.borrow_mut().update_music();
+    }
+
+    pub fn update_voice(&self) {
+        self.sound.borrow_mut().update_voice();
     }
 
     pub fn get_variable<T, A>(&self, index: A) -> Option<T>
@@ -324,3 +954,24 @@ impl <'a> Director<'a> {
     }
 
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn touches_are_held_for_the_elapsed_update_time() {
+        let director = Director::new();
+        director.input.borrow_mut().push_events(vec![InputEvent::FingerDown { finger_id: 1, x: 0.5, y: 0.5 }]);
+        director.update_input_state();
+        director.tick_clock(400_000);
+        director.update_input_state();
+        director.update_input_state();
+        assert!(director.input.borrow().get_touch_gestures().is_empty());
+        director.tick_clock(100_000);
+        director.update_input_state();
+        assert_eq!(director.input.borrow().get_touch_gestures().len(), 1);
+    }
+
+}
@@ -36,6 +36,10 @@ impl NodeDirector {
         node.cloned().unwrap()
     }
 
+    pub fn find_nodelike(&self, id: &NodeId) -> Option<Rc<dyn NodeLike>> {
+        self.nodelikes.get(id).cloned()
+    }
+
     pub fn destroy(&mut self, id: &NodeId) {
         self.anynodes.remove(id);
         self.nodelikes.remove(id);
@@ -0,0 +1,148 @@
+use std::rc::Rc;
+use ::node::{ NodeId, NodeLike, ConflictType };
+use ::util::parameter::{ Point, Rect, Circle, PointerEventType };
+
+const DRAG_THRESHOLD: f32 = 4.0;
+
+pub struct PointerDispatch {
+    pub event_type: PointerEventType,
+    pub path: Vec<NodeId>,
+    pub bubbles: bool,
+    pub start_position: Point,
+    pub drag_source: Option<NodeId>
+}
+
+pub struct PointerDirector {
+    position: Point,
+    hover_path: Vec<NodeId>,
+    press_path: Option<Vec<NodeId>>,
+    press_position: Point,
+    drag_path: Option<Vec<NodeId>>
+}
+
+impl PointerDirector {
+
+    pub fn new() -> Self {
+        Self {
+            position: Point::new(-1, -1),
+            hover_path: Vec::new(),
+            press_path: None,
+            press_position: Point::new(-1, -1),
+            drag_path: None
+        }
+    }
+
+    pub fn update(&mut self, position: Point, path: Vec<NodeId>, press_state: (bool, bool, bool)) -> Vec<PointerDispatch> {
+        let (press_start, pressing, pressed) = press_state;
+        let mut dispatches: Vec<PointerDispatch> = Vec::new();
+        for id in self.hover_path.iter().rev().filter(|id| !path.contains(id)) {
+            dispatches.push(self.single(PointerEventType::Leave, id.clone()));
+        }
+        for id in path.iter().filter(|id| !self.hover_path.contains(id)) {
+            dispatches.push(self.single(PointerEventType::Enter, id.clone()));
+        }
+        self.hover_path = path.clone();
+        if press_start {
+            self.press_path = Some(path.clone());
+            self.press_position = position.clone();
+        }
+        if pressing {
+            if let Some(drag_path) = self.drag_path.clone() {
+                if position != self.position {
+                    dispatches.push(self.bubble(PointerEventType::Drag, drag_path, None));
+                }
+            } else if let Some(press_path) = self.press_path.clone() {
+                if !press_path.is_empty() && position.distance(&self.press_position) >= DRAG_THRESHOLD {
+                    self.drag_path = Some(press_path.clone());
+                    dispatches.push(self.bubble(PointerEventType::DragStart, press_path, None));
+                }
+            }
+        }
+        if pressed {
+            if let Some(drag_path) = self.drag_path.take() {
+                let source = drag_path.last().cloned();
+                dispatches.push(self.bubble(PointerEventType::Drop, path.clone(), source));
+            } else if let Some(press_path) = self.press_path.clone() {
+                let common: Vec<NodeId> = press_path.iter().zip(path.iter()).take_while(|(a, b)| a == b).map(|(a, _)| a.clone()).collect();
+                dispatches.push(self.bubble(PointerEventType::Click, common, None));
+            }
+            self.press_path = None;
+        }
+        self.position = position;
+        dispatches.into_iter().filter(|d| !d.path.is_empty()).collect()
+    }
+
+    fn single(&self, event_type: PointerEventType, id: NodeId) -> PointerDispatch {
+        PointerDispatch {
+            event_type: event_type,
+            path: vec!(id),
+            bubbles: false,
+            start_position: self.press_position.clone(),
+            drag_source: None
+        }
+    }
+
+    fn bubble(&self, event_type: PointerEventType, path: Vec<NodeId>, drag_source: Option<NodeId>) -> PointerDispatch {
+        PointerDispatch {
+            event_type: event_type,
+            path: path,
+            bubbles: true,
+            start_position: self.press_position.clone(),
+            drag_source: drag_source
+        }
+    }
+
+    pub fn hit_test(root: Rc<dyn NodeLike>, point: &Point) -> Vec<Rc<dyn NodeLike>> {
+        if !root.inner_get_visible() || !Self::contains_local_point(root.clone(), point) { return Vec::new(); }
+        let mut path = vec!(root.clone());
+        for child in root.inner_get_children().into_iter().rev() {
+            let local = Self::convert_to_child_point(child.clone(), point);
+            let mut child_path = Self::hit_test(child, &local);
+            if !child_path.is_empty() {
+                path.append(&mut child_path);
+                break;
+            }
+        }
+        path
+    }
+
+    pub fn convert_to_local_point(node: Rc<dyn NodeLike>, point: &Point) -> Point {
+        match node.inner_get_parent() {
+            Some(parent) => {
+                let parent_point = Self::convert_to_local_point(parent.clone(), point);
+                Self::convert_to_child_point(node, &parent_point)
+            },
+            None => point.clone()
+        }
+    }
+
+    fn convert_to_child_point(child: Rc<dyn NodeLike>, point: &Point) -> Point {
+        let rect = child.inner_get_render_rect();
+        let size = child.inner_get_size();
+        let angle = (*child.inner_get_rotation()).to_radians();
+        let center = (rect.x() as f64 + rect.width() as f64 / 2.0, rect.y() as f64 + rect.height() as f64 / 2.0);
+        let d = (point.x() as f64 - center.0, point.y() as f64 - center.1);
+        let rotated = (
+            d.0 * angle.cos() + d.1 * angle.sin() + center.0,
+            -d.0 * angle.sin() + d.1 * angle.cos() + center.1
+        );
+        let magni = (
+            if rect.width() == 0 { 1.0 } else { size.width() as f64 / rect.width() as f64 },
+            if rect.height() == 0 { 1.0 } else { size.height() as f64 / rect.height() as f64 }
+        );
+        Point::new(
+            ((rotated.0 - rect.x() as f64) * magni.0).floor() as i32,
+            ((rotated.1 - rect.y() as f64) * magni.1).floor() as i32
+        )
+    }
+
+    fn contains_local_point(node: Rc<dyn NodeLike>, point: &Point) -> bool {
+        let size = node.inner_get_size();
+        let rect = Rect::new(0, 0, size.width(), size.height());
+        match node.inner_get_conflict_type() {
+            ConflictType::Square => rect.contains_point(**point),
+            ConflictType::Circle => Circle::from(rect).contains_point(point.clone())
+        }
+    }
+
+}
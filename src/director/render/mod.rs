@@ -11,7 +11,8 @@ use ::util::{ with_context };
 use ::util::parameter::{ Size, Rect, Point, Opacity };
 use ::director::resource::{ ResourceDirector };
 use sdl2::render::{ Texture, BlendMode };
-use sdl2::pixels::{ Color };
+use sdl2::pixels::{ Color, PixelFormatEnum };
+use image::{ RgbaImage };
 use std::intrinsics::transmute;
 
 #[derive(Clone)]
@@ -51,10 +52,13 @@ impl RenderTree {
 pub struct RenderDirector<'a> {
     application: Option<Rc<dyn Application>>,
     resource: ResourceDirector<'a>,
+    window_size: Size,
     resolution_size: Size,
     resolution_policy: ResolutionPolicy,
     render_canvas_dest: Option<Rect>,
     render_tree_nodes: HashMap<NodeId, Rc<RenderTree>>,
+    last_render_tree_nodes: HashMap<NodeId, Rc<RenderTree>>,
+    last_canvas: Option<Texture<'a>>,
 }
 
 impl <'a> RenderDirector<'a> {
@@ -63,15 +67,18 @@ impl <'a> RenderDirector<'a> {
         Self {
             application: None,
             resource: ResourceDirector::new(),
+            window_size: Size::new(0, 0),
             resolution_size: Size::new(0, 0),
             resolution_policy: ResolutionPolicy::ExactFit,
             render_canvas_dest: None,
             render_tree_nodes: HashMap::new(),
+            last_render_tree_nodes: HashMap::new(),
+            last_canvas: None,
         }
     }
 
-    fn generate_render_canvas_dest(app: Rc<dyn Application>) -> Option<Rect> {
-        let (window_size, rsize, policy) = (app.window_size(), app.resolution_size(), app.resolution_policy());
+    fn generate_render_canvas_dest(app: Rc<dyn Application>, window_size: &Size) -> Option<Rect> {
+        let (rsize, policy) = (app.resolution_size(), app.resolution_policy());
         if &policy == &ResolutionPolicy::ExactFit { return None; }
         let per_size = (window_size.width() as f32 / rsize.width() as f32, window_size.height() as f32 / rsize.height() as f32);
         let use_per_size = Self::choice_per_size_from_policy(per_size.0, per_size.1, policy);
@@ -94,9 +101,7 @@ impl <'a> RenderDirector<'a> {
 
     pub fn convert_window_point_to_resolution_point(&self, point: &Point) -> Point {
         if point.x() < 0 && point.y() < 0 { return point.clone(); }
-        let application = self.get_application();
-        let window_size = application.window_size();
-        let render_dest = self.render_canvas_dest.clone().unwrap_or(Rect::new(0, 0, window_size.width(), window_size.height()));
+        let render_dest = self.get_render_dest();
         if render_dest.x() > point.x() ||
             render_dest.x() + (render_dest.width() as i32) < point.x() ||
             render_dest.y() > point.y() ||
@@ -108,11 +113,43 @@ impl <'a> RenderDirector<'a> {
         Point::new((normalized_point.x() as f32 * magni.0) as i32, (normalized_point.y() as f32 * magni.1) as i32)
     }
 
+    pub fn convert_window_delta_to_resolution_delta(&self, delta: &Point) -> Point {
+        let render_dest = self.get_render_dest();
+        let magni: (f32, f32) = (self.resolution_size.width() as f32 / render_dest.width() as f32, self.resolution_size.height() as f32 / render_dest.height() as f32);
+        Point::new((delta.x() as f32 * magni.0).round() as i32, (delta.y() as f32 * magni.1).round() as i32)
+    }
+
+    pub fn convert_resolution_rect_to_window_rect(&self, rect: &Rect) -> Rect {
+        let render_dest = self.get_render_dest();
+        let magni: (f32, f32) = (render_dest.width() as f32 / self.resolution_size.width() as f32, render_dest.height() as f32 / self.resolution_size.height() as f32);
+        Rect::new(
+            render_dest.x() + (rect.x() as f32 * magni.0) as i32,
+            render_dest.y() + (rect.y() as f32 * magni.1) as i32,
+            (rect.width() as f32 * magni.0).round() as u32,
+            (rect.height() as f32 * magni.1).round() as u32
+        )
+    }
+
+    fn get_render_dest(&self) -> Rect {
+        self.render_canvas_dest.clone().unwrap_or(Rect::new(0, 0, self.window_size.width(), self.window_size.height()))
+    }
+
     pub fn set_application(&mut self, application: Rc<dyn Application>) {
         self.application = Some(application.clone());
+        self.window_size = application.window_size();
         self.resolution_size = application.resolution_size();
         self.resolution_policy = application.resolution_policy();
-        self.render_canvas_dest = Self::generate_render_canvas_dest(application);
+        self.render_canvas_dest = Self::generate_render_canvas_dest(application, &self.window_size);
+    }
+
+    pub fn get_window_size(&self) -> Size {
+        self.window_size.clone()
+    }
+
+    pub fn set_window_size(&mut self, window_size: Size) {
+        if self.window_size == window_size { return; }
+        self.window_size = window_size;
+        self.render_canvas_dest = Self::generate_render_canvas_dest(self.get_application(), &self.window_size);
     }
 
     fn get_application(&self) -> Rc<dyn Application> {
@@ -174,7 +211,7 @@ impl <'a> RenderDirector<'a> {
         if self.resolution_size != resolution_size || self.resolution_policy != resolution_policy {
             self.resolution_size = resolution_size;
             self.resolution_policy = resolution_policy;
-            self.render_canvas_dest = Self::generate_render_canvas_dest(application);
+            self.render_canvas_dest = Self::generate_render_canvas_dest(application, &self.window_size);
         }
     }
 
@@ -340,10 +377,39 @@ impl <'a> RenderDirector<'a> {
             can.copy(&canvas, None, self.render_canvas_dest.clone().map(|e| e.into())).unwrap();
             can.present();
         });
-        self.render_tree_nodes = HashMap::new();
+        self.last_canvas = Some(canvas);
+        self.last_render_tree_nodes = std::mem::replace(&mut self.render_tree_nodes, HashMap::new());
         status
     }
 
+    pub fn capture_screen(&self) -> Option<RgbaImage> {
+        if let Some(canvas) = &self.last_canvas {
+            return Some(self.read_texture_pixels(canvas));
+        }
+        None
+    }
+
+    pub fn capture_node(&mut self, node: Rc<dyn NodeLike>) -> Option<RgbaImage> {
+        let render_tree = self.last_render_tree_nodes.get(&node.inner_id()).cloned();
+        if render_tree.is_none() { return None; }
+        let texture = if let Some(texture) = self.render_inner_canvas(render_tree.unwrap()) {
+            texture
+        } else {
+            Rc::new(self.create_sub_canvas(node.inner_get_size()))
+        };
+        Some(self.read_texture_pixels(&texture))
+    }
+
+    fn read_texture_pixels(&self, texture: &Texture<'a>) -> RgbaImage {
+        let query = texture.query();
+        let mut sub_canvas = self.clone_texture(texture);
+        let mut pixels: Vec<u8> = Vec::new();
+        with_context(|c| &mut c.canvas).with_texture_canvas(&mut sub_canvas, |c| {
+            pixels = c.read_pixels(None, PixelFormatEnum::RGBA32).unwrap();
+        }).unwrap();
+        RgbaImage::from_raw(query.width, query.height, pixels).unwrap()
+    }
+
     pub fn destroy_render_cache(&mut self, key: &ResourceKey) {
         self.resource.destroy_render_cache(key);
     }
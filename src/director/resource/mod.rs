@@ -168,6 +168,16 @@ impl <'a> ResourceDirector<'a> {
         }
     }
 
+    pub fn decode_se(&mut self, path: &str) -> Result<Chunk, String> {
+        let resource_key = self.generate_resource_key(
+            path,
+            ResourceType::SE
+        );
+        let plain_data = self.load_plain_data(&resource_key.path());
+        let rwops = RWops::from_bytes(&plain_data)?;
+        rwops.load_wav()
+    }
+
     pub fn load_font_from_resource_key(&self, key: Rc<::resource::Font>) -> Rc<Font<'a, 'a>> {
         let resource_key = key.key();
         self.fonts.get(&resource_key).unwrap().clone()
@@ -0,0 +1,43 @@
+use ::util::parameter::{ SoundBus };
+
+pub trait AudioBackend {
+
+    fn add_alias(&mut self, name: &str, path: &str);
+
+    fn play_music(&mut self, path: &str, loops: i32, fade_in: i32, position: f64);
+
+    fn stop_music(&mut self, fade_out: i32);
+
+    fn play_music_tail(&mut self, path: &str, position: f64, fade_out: i32);
+
+    fn pause_music(&mut self);
+
+    fn resume_music(&mut self);
+
+    fn is_music_playing(&self) -> bool;
+
+    fn set_music_position(&mut self, position: f64);
+
+    fn set_music_volume(&mut self, volume: f32);
+
+    fn allocate_channels(&mut self, channels: i32);
+
+    fn play_chunk(&mut self, path: &str, bus: SoundBus, volume: f32) -> i32;
+
+    fn stop_channel(&mut self, channel: i32);
+
+    fn set_channel_volume(&mut self, channel: i32, volume: f32);
+
+    fn is_channel_playing(&self, channel: i32) -> bool;
+
+    fn is_channel_paused(&self, channel: i32) -> bool;
+
+    fn pause_channel(&mut self, channel: i32);
+
+    fn resume_channel(&mut self, channel: i32);
+
+    fn clean_channels(&mut self) { }
+
+    fn take_records(&mut self) -> Vec<String> { Vec::new() }
+
+}
@@ -0,0 +1,176 @@
+use ::director::resource::ResourceDirector;
+use ::director::sound::AudioBackend;
+use ::util::parameter::{ SoundBus };
+use ::util::{ output_warning_log };
+use sdl2::mixer::{ Music, Chunk, Channel, MAX_VOLUME };
+use sdl2::sys::mixer::{ Mix_QuickLoad_RAW };
+
+const RESERVED_CHANNELS: i32 = 2;
+const VOICE_CHANNEL: i32 = 0;
+const MUSIC_TAIL_CHANNEL: i32 = 1;
+
+struct MusicTail {
+    tail: Chunk,
+    _source: Chunk
+}
+
+pub struct MixerBackend<'a> {
+    resource: ResourceDirector<'a>,
+    music_volume: f32,
+    music_tail: Option<MusicTail>
+}
+
+impl <'a> MixerBackend<'a> {
+
+    pub fn new() -> Self {
+        Self {
+            resource: ResourceDirector::new(),
+            music_volume: 1.0,
+            music_tail: None
+        }
+    }
+
+    fn mixer_volume(volume: f32) -> i32 {
+        (volume * MAX_VOLUME as f32).round() as i32
+    }
+
+    fn load_music_tail(&mut self, path: &str, position: f64) -> Result<MusicTail, String> {
+        let source = self.resource.decode_se(path)?;
+        let (frequency, format, channels) = sdl2::mixer::query_spec()?;
+        let frame_size = (format & 0xFF) as usize / 8 * channels as usize;
+        let offset = (position.max(0.0) * frequency as f64) as usize * frame_size;
+        let tail = unsafe {
+            let raw = &*source.raw;
+            if offset >= raw.alen as usize { return Err(format!("music tail is past the end: {} at {}", path, position)); }
+            Mix_QuickLoad_RAW(raw.abuf.add(offset), raw.alen - offset as u32)
+        };
+        if tail.is_null() { return Err(sdl2::get_error()); }
+        Ok(MusicTail { tail: Chunk { raw: tail, owned: true }, _source: source })
+    }
+
+    fn release_music_tail(&mut self) {
+        Channel(MUSIC_TAIL_CHANNEL).halt();
+        self.music_tail = None;
+    }
+
+}
+
+impl <'a> AudioBackend for MixerBackend<'a> {
+
+    fn add_alias(&mut self, name: &str, path: &str) {
+        self.resource.add_alias(name, path);
+    }
+
+    fn play_music(&mut self, path: &str, loops: i32, fade_in: i32, position: f64) {
+        let m = self.resource.load_music(path);
+        if let Err(e) = m.fade_in_from_pos(loops, fade_in, position) {
+            output_warning_log(&format!("failed to play {} from {}, playing from the start: {}", path, position, e));
+            if let Err(e) = m.fade_in(loops, fade_in) {
+                output_warning_log(&format!("failed to play {}: {}", path, e));
+            }
+        }
+    }
+
+    fn stop_music(&mut self, fade_out: i32) {
+        if fade_out <= 0 {
+            Music::halt();
+            self.release_music_tail();
+        } else {
+            Music::fade_out(fade_out).unwrap();
+            Channel(MUSIC_TAIL_CHANNEL).fade_out(fade_out);
+        }
+    }
+
+    fn play_music_tail(&mut self, path: &str, position: f64, fade_out: i32) {
+        self.release_music_tail();
+        let music_tail = match self.load_music_tail(path, position) {
+            Ok(music_tail) => music_tail,
+            Err(e) => {
+                output_warning_log(&format!("failed to crossfade from {}: {}", path, e));
+                return;
+            }
+        };
+        let channel = Channel(MUSIC_TAIL_CHANNEL);
+        channel.set_volume(Self::mixer_volume(self.music_volume));
+        if let Err(e) = channel.play(&music_tail.tail, 0) {
+            output_warning_log(&format!("failed to crossfade from {}: {}", path, e));
+            return;
+        }
+        channel.fade_out(fade_out);
+        self.music_tail = Some(music_tail);
+    }
+
+    fn pause_music(&mut self) {
+        Music::pause();
+        Channel(MUSIC_TAIL_CHANNEL).pause();
+    }
+
+    fn resume_music(&mut self) {
+        Music::resume();
+        Channel(MUSIC_TAIL_CHANNEL).resume();
+    }
+
+    fn is_music_playing(&self) -> bool {
+        Music::is_playing()
+    }
+
+    fn set_music_position(&mut self, position: f64) {
+        if let Err(e) = Music::set_pos(position) {
+            output_warning_log(&format!("failed to seek music to {}, playing from the start: {}", position, e));
+            Music::rewind();
+        }
+    }
+
+    fn set_music_volume(&mut self, volume: f32) {
+        self.music_volume = volume;
+        Music::set_volume(Self::mixer_volume(volume));
+    }
+
+    fn allocate_channels(&mut self, channels: i32) {
+        sdl2::mixer::allocate_channels(channels + RESERVED_CHANNELS);
+        sdl2::mixer::reserve_channels(RESERVED_CHANNELS);
+    }
+
+    fn play_chunk(&mut self, path: &str, bus: SoundBus, volume: f32) -> i32 {
+        let se = self.resource.load_se(path);
+        let channel = match bus {
+            SoundBus::Voice => Channel(VOICE_CHANNEL),
+            _ => Channel::all()
+        };
+        let channel = channel.play(&se, 0).unwrap();
+        channel.set_volume(Self::mixer_volume(volume));
+        let Channel(id) = channel;
+        id
+    }
+
+    fn stop_channel(&mut self, channel: i32) {
+        Channel(channel).halt();
+    }
+
+    fn set_channel_volume(&mut self, channel: i32, volume: f32) {
+        Channel(channel).set_volume(Self::mixer_volume(volume));
+    }
+
+    fn is_channel_playing(&self, channel: i32) -> bool {
+        Channel(channel).is_playing()
+    }
+
+    fn is_channel_paused(&self, channel: i32) -> bool {
+        Channel(channel).is_paused()
+    }
+
+    fn pause_channel(&mut self, channel: i32) {
+        Channel(channel).pause();
+    }
+
+    fn resume_channel(&mut self, channel: i32) {
+        Channel(channel).resume();
+    }
+
+    fn clean_channels(&mut self) {
+        if self.music_tail.is_some() && !Channel(MUSIC_TAIL_CHANNEL).is_playing() {
+            self.music_tail = None;
+        }
+    }
+
+}
@@ -1,94 +1,550 @@
+mod audio_backend;
+mod mixer_backend;
+mod null_backend;
+
 use std::rc::Rc;
-use std::collections::HashMap;
-use ::director::resource::ResourceDirector;
+use std::collections::{ HashMap, VecDeque };
+use std::time::{ Instant };
+use ::application::{ AudioMode };
 use ::resource::{SE, ResourceKey, ResourceType };
-use ::util::parameter::{MusicOption};
-use sdl2::mixer::{ Music, Channel };
-use uuid::Uuid;
+use ::util::parameter::{ MusicOption, SEOption, SoundBus };
+
+pub use self::audio_backend::*;
+pub use self::mixer_backend::*;
+pub use self::null_backend::*;
+
+struct MusicTrack {
+    path: String,
+    option: MusicOption,
+    loops: i32,
+    base_position: f64,
+    started_at: Instant,
+    paused_at: Option<Instant>
+}
+
+impl MusicTrack {
+
+    fn new(path: &str, option: MusicOption, position: f64) -> Self {
+        Self {
+            path: path.to_owned(),
+            loops: option.loops,
+            option: option,
+            base_position: position,
+            started_at: Instant::now(),
+            paused_at: None
+        }
+    }
+
+    fn position(&self) -> f64 {
+        let now = self.paused_at.unwrap_or_else(Instant::now);
+        self.base_position + (now - self.started_at).as_secs_f64()
+    }
+
+    fn seek(&mut self, position: f64) {
+        self.base_position = position;
+        self.started_at = Instant::now();
+        if self.paused_at.is_some() { self.paused_at = Some(self.started_at); }
+    }
+
+    fn pause(&mut self) {
+        if self.paused_at.is_none() { self.paused_at = Some(Instant::now()); }
+    }
+
+    fn resume(&mut self) {
+        if let Some(paused_at) = self.paused_at.take() {
+            self.started_at += Instant::now() - paused_at;
+        }
+    }
+
+    fn loop_target(&self, playing: bool) -> Option<f64> {
+        if self.loops == 0 { return None; }
+        let loop_start = self.option.loop_start.unwrap_or(0.0);
+        match self.option.loop_end {
+            Some(loop_end) => {
+                let position = self.position();
+                if position >= loop_end { Some(loop_start + (position - loop_end)) } else { None }
+            },
+            None if self.option.loop_start.is_some() && !playing => Some(loop_start),
+            None => None
+        }
+    }
+
+    fn mixer_loops(option: &MusicOption, loops: i32) -> i32 {
+        match (option.loop_start, option.loop_end) {
+            (_, Some(_)) if loops != 0 => -1,
+            (None, None) => loops,
+            _ => 1
+        }
+    }
+
+}
+
+const DEFAULT_MAX_SE_CHANNELS: usize = 16;
+
+struct SEChannel {
+    id: u64,
+    path: String,
+    channel: i32,
+    volume: f32,
+    priority: i32
+}
 
 pub struct SoundDirector<'a> {
-    resource: ResourceDirector<'a>,
-    ses: HashMap<String, Channel>
+    backend: Box<dyn AudioBackend + 'a>,
+    ses: Vec<SEChannel>,
+    max_se_channels: usize,
+    next_se_id: u64,
+    volumes: HashMap<SoundBus, f32>,
+    music_volume: f32,
+    music: Option<MusicTrack>,
+    music_queue: VecDeque<(String, MusicOption)>,
+    music_paused: bool,
+    voice: Option<SEChannel>,
+    voice_ducking: f32,
+    ducked: bool,
+    suspended_channels: Option<Vec<i32>>,
+    music_suspended: bool
 }
 
 impl <'a> SoundDirector<'a> {
 
     pub fn new() -> Self {
         Self {
-            resource: ResourceDirector::new(),
-            ses: HashMap::new()
+            backend: Box::new(NullBackend::new()),
+            ses: Vec::new(),
+            max_se_channels: DEFAULT_MAX_SE_CHANNELS,
+            next_se_id: 0,
+            volumes: HashMap::new(),
+            music_volume: 1.0,
+            music: None,
+            music_queue: VecDeque::new(),
+            music_paused: false,
+            voice: None,
+            voice_ducking: 0.5,
+            ducked: false,
+            suspended_channels: None,
+            music_suspended: false
         }
     }
 
+    pub fn initialize(&mut self, audio_mode: AudioMode) {
+        self.backend = match audio_mode {
+            AudioMode::Mixer => Box::new(MixerBackend::new()),
+            AudioMode::Null => Box::new(NullBackend::new())
+        };
+        self.backend.allocate_channels(self.max_se_channels as i32);
+    }
+
+    pub fn take_audio_records(&mut self) -> Vec<String> {
+        self.backend.take_records()
+    }
+
+    pub fn get_volume(&self, bus: SoundBus) -> f32 {
+        self.volumes.get(&bus).cloned().unwrap_or(1.0)
+    }
+
+    pub fn set_volume(&mut self, bus: SoundBus, volume: f32) {
+        self.volumes.insert(bus, volume.max(0.0).min(1.0));
+        self.apply_music_volume();
+        let volumes: Vec<(i32, f32)> = self.ses.iter()
+            .map(|se| (se.channel, self.effective_volume(SoundBus::SE, se.volume)))
+            .chain(self.voice.iter().map(|v| (v.channel, self.effective_volume(SoundBus::Voice, v.volume))))
+            .collect();
+        for (channel, volume) in volumes {
+            self.backend.set_channel_volume(channel, volume);
+        }
+    }
+
+    pub fn get_music_volume(&self) -> f32 {
+        self.music_volume
+    }
+
+    pub fn set_music_volume(&mut self, volume: f32) {
+        self.music_volume = volume.max(0.0).min(1.0);
+        self.apply_music_volume();
+    }
+
+    pub fn get_voice_ducking(&self) -> f32 {
+        self.voice_ducking
+    }
+
+    pub fn set_voice_ducking(&mut self, ducking: f32) {
+        if ducking < 0.0 || ducking > 1.0 { panic!(format!("invalid ducking: {}", ducking)); }
+        self.voice_ducking = ducking;
+        self.apply_music_volume();
+    }
+
+    fn apply_music_volume(&mut self) {
+        let ducking = if self.ducked { self.voice_ducking } else { 1.0 };
+        let volume = self.effective_volume(SoundBus::BGM, self.music_volume * ducking);
+        self.backend.set_music_volume(volume);
+    }
+
+    fn effective_volume(&self, bus: SoundBus, volume: f32) -> f32 {
+        self.get_volume(SoundBus::Master) * self.get_volume(bus) * volume
+    }
+
     pub fn add_alias(&mut self, name: &str, path: &str) {
-        self.resource.add_alias(name, path);
+        self.backend.add_alias(name, path);
     }
 
     pub fn play_music(&mut self, path: &str, option: MusicOption) {
-        let m = self.resource.load_music(path);
-        let loops = option.loops;
+        let playing = !self.music_paused && self.backend.is_music_playing();
+        match (option.crossfade, self.music.as_ref().filter(|_| playing)) {
+            (Some(crossfade), Some(track)) => {
+                self.backend.play_music_tail(&track.path, track.position(), crossfade);
+                self.start_music(path, MusicOption { fade_in: Some(crossfade), ..option });
+            },
+            _ => self.start_music(path, option)
+        }
+    }
+
+    fn start_music(&mut self, path: &str, option: MusicOption) {
+        let loops = MusicTrack::mixer_loops(&option, option.loops);
         let fade_in = option.fade_in.clone().unwrap_or(0);
         let position = option.position.clone().unwrap_or(0.0);
-        m.fade_in_from_pos(loops, fade_in, position).unwrap();
+        self.apply_music_volume();
+        self.backend.play_music(path, loops, fade_in, position);
+        self.music_paused = false;
+        self.music = Some(MusicTrack::new(path, option, position));
+    }
+
+    pub fn queue_music(&mut self, path: &str, option: MusicOption) {
+        self.music_queue.push_back((path.to_owned(), option));
+    }
+
+    pub fn clear_music_queue(&mut self) {
+        self.music_queue.clear();
+    }
+
+    pub fn stop_music(&mut self, fade_out: i32) {
+        self.music = None;
+        self.music_queue.clear();
+        self.music_paused = false;
+        self.backend.stop_music(fade_out);
+    }
+
+    pub fn pause_music(&mut self) {
+        self.music_paused = true;
+        if let Some(track) = &mut self.music { track.pause(); }
+        self.backend.pause_music();
+    }
+
+    pub fn resume_music(&mut self) {
+        self.music_paused = false;
+        if let Some(track) = &mut self.music { track.resume(); }
+        self.backend.resume_music();
+    }
+
+    pub fn is_music_paused(&self) -> bool {
+        self.music_paused
+    }
+
+    pub fn get_current_music(&self) -> Option<String> {
+        self.music.as_ref().map(|m| m.path.clone())
+    }
+
+    pub fn get_music_position(&self) -> Option<f64> {
+        self.music.as_ref().map(|m| m.position())
     }
 
-    pub fn stop_music(&self, fade_out: i32) {
-        if fade_out <= 0 {
-            Music::halt();
-        } else {
-            Music::fade_out(fade_out).unwrap();
+    pub fn update_music(&mut self) {
+        if self.music_paused || self.music_suspended { return; }
+        let playing = self.backend.is_music_playing();
+        let mut finished = !playing;
+        if let Some(track) = &mut self.music {
+            if let Some(target) = track.loop_target(playing) {
+                if track.loops > 0 { track.loops -= 1; }
+                if playing && track.loops != 0 {
+                    self.backend.set_music_position(target);
+                } else {
+                    self.backend.play_music(&track.path, MusicTrack::mixer_loops(&track.option, track.loops), 0, target);
+                }
+                track.seek(target);
+                finished = false;
+            }
+        }
+        if finished {
+            self.music = None;
+        }
+        if self.music.is_none() && !self.backend.is_music_playing() {
+            if let Some((path, option)) = self.music_queue.pop_front() {
+                self.start_music(&path, option);
+            }
         }
     }
 
-    pub fn play_se(&mut self, path: &str) -> Rc<SE> {
-        let channel_id = self.generate_new_channel();
-        let se = self.resource.load_se(path);
-        let channel = self.ses.get(&channel_id).unwrap();
-        channel.play(&se, 0).unwrap();
+    pub fn get_max_se_channels(&self) -> usize {
+        self.max_se_channels
+    }
+
+    pub fn set_max_se_channels(&mut self, channels: usize) {
+        self.max_se_channels = channels;
+        while self.ses.len() > channels {
+            let index = self.find_se_victim(None).unwrap();
+            self.halt_se(index);
+        }
+        self.backend.allocate_channels(channels as i32);
+    }
+
+    pub fn play_se(&mut self, path: &str, option: SEOption) -> Rc<SE> {
+        let id = self.generate_se_id();
+        let se = Rc::new(SE::new(ResourceKey::new(path, ResourceType::SE), id));
+        self.clean_se();
+        if let Some(max_instances) = option.max_instances {
+            let instances: Vec<usize> = self.ses.iter().enumerate()
+                .filter(|(_, se)| se.path == path)
+                .map(|(index, _)| index)
+                .collect();
+            if instances.len() >= max_instances {
+                if max_instances == 0 { return se; }
+                self.halt_se(instances[0]);
+            }
+        }
+        if self.ses.len() >= self.max_se_channels {
+            match self.find_se_victim(Some(option.priority)) {
+                Some(index) => self.halt_se(index),
+                None => return se
+            }
+        }
+        let volume = self.effective_volume(SoundBus::SE, option.volume);
+        let channel = self.backend.play_chunk(path, SoundBus::SE, volume);
+        self.ses.push(SEChannel {
+            id: id,
+            path: path.to_owned(),
+            channel: channel,
+            volume: option.volume,
+            priority: option.priority
+        });
+        se
+    }
+
+    fn find_se_victim(&self, priority: Option<i32>) -> Option<usize> {
+        self.ses.iter().enumerate()
+            .filter(|(_, se)| priority.map(|p| se.priority <= p).unwrap_or(true))
+            .min_by_key(|(_, se)| se.priority)
+            .map(|(index, _)| index)
+    }
+
+    fn halt_se(&mut self, index: usize) {
+        let se = self.ses.remove(index);
+        self.backend.stop_channel(se.channel);
+    }
+
+    pub fn stop_se(&mut self, se: Rc<SE>) {
+        if let Some(index) = self.ses.iter().position(|s| s.id == se.id()) {
+            self.halt_se(index);
+        }
+        if self.voice.as_ref().map(|v| v.id == se.id()).unwrap_or(false) {
+            self.stop_voice();
+        }
+    }
+
+    pub fn play_voice(&mut self, path: &str, option: SEOption) -> Rc<SE> {
+        self.stop_voice();
+        let id = self.generate_se_id();
+        let volume = self.effective_volume(SoundBus::Voice, option.volume);
+        let channel = self.backend.play_chunk(path, SoundBus::Voice, volume);
+        self.voice = Some(SEChannel {
+            id: id,
+            path: path.to_owned(),
+            channel: channel,
+            volume: option.volume,
+            priority: option.priority
+        });
+        self.update_voice();
         Rc::new(SE::new(
             ResourceKey::new(path, ResourceType::SE),
-            channel_id
+            id
         ))
     }
 
-    pub fn stop_se(&self, se: Rc<SE>) {
-        if let Some(channel) = self.ses.get(&se.channel()) {
-            channel.halt();
+    pub fn stop_voice(&mut self) {
+        if let Some(voice) = self.voice.take() {
+            self.backend.stop_channel(voice.channel);
         }
+        self.update_voice();
     }
 
-    pub fn stop_all_se(&self) {
-        for (_, se) in &self.ses {
-            se.halt();
+    pub fn is_voice_playing(&self) -> bool {
+        match &self.voice {
+            Some(voice) => self.backend.is_channel_playing(voice.channel),
+            None => false
         }
     }
 
-    pub fn clean_se(&mut self, seed: usize) {
-        let key = {
-            let channels: Vec<&String> = self.ses.keys().collect();
-            if channels.len() == 0 { return; }
-            let index = seed % channels.len();
-            channels.get(index).unwrap().to_string()
-        };
-        let se = self.ses.get(&key).unwrap();
-        if !se.is_paused() && !se.is_playing() {
-            self.ses.remove(&key);
+    pub fn update_voice(&mut self) {
+        if !self.is_voice_playing() {
+            self.voice = None;
+        }
+        let ducked = self.voice.is_some();
+        if ducked != self.ducked {
+            self.ducked = ducked;
+            self.apply_music_volume();
         }
     }
 
-    fn generate_new_channel(&mut self) -> String {
-        let channel_id = self.generate_channel_id();
-        self.ses.insert(channel_id.clone(), Channel(-1));
-        channel_id
+    pub fn stop_all_se(&mut self) {
+        for se in self.ses.drain(..) {
+            self.backend.stop_channel(se.channel);
+        }
     }
 
-    fn generate_channel_id(&self) -> String {
-        loop {
-            let id = Uuid::new_v4().to_string();
-            if self.ses.get(&id).is_none() {
-                return id;
+    pub fn pause_all(&mut self) {
+        if self.suspended_channels.is_some() { return; }
+        let channels: Vec<i32> = self.ses.iter().chain(self.voice.iter())
+            .map(|se| se.channel)
+            .filter(|c| self.backend.is_channel_playing(*c) && !self.backend.is_channel_paused(*c))
+            .collect();
+        for channel in &channels {
+            self.backend.pause_channel(*channel);
+        }
+        self.suspended_channels = Some(channels);
+        self.music_suspended = !self.music_paused && self.backend.is_music_playing();
+        if self.music_suspended {
+            if let Some(track) = &mut self.music { track.pause(); }
+            self.backend.pause_music();
+        }
+    }
+
+    pub fn resume_all(&mut self) {
+        if let Some(channels) = self.suspended_channels.take() {
+            for channel in channels {
+                self.backend.resume_channel(channel);
             }
         }
+        if self.music_suspended && !self.music_paused {
+            if let Some(track) = &mut self.music { track.resume(); }
+            self.backend.resume_music();
+        }
+        self.music_suspended = false;
+    }
+
+    pub fn clean_se(&mut self) {
+        self.backend.clean_channels();
+        let backend = &self.backend;
+        self.ses.retain(|se| backend.is_channel_playing(se.channel) || backend.is_channel_paused(se.channel));
+    }
+
+    fn generate_se_id(&mut self) -> u64 {
+        self.next_se_id += 1;
+        self.next_se_id
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn null_chunks_finish_on_the_next_clean() {
+        let mut sound = SoundDirector::new();
+        sound.play_se("se.wav", SEOption::default());
+        sound.play_voice("voice.wav", SEOption::default());
+        assert!(sound.is_voice_playing());
+        assert!(sound.ducked);
+        sound.clean_se();
+        sound.update_voice();
+        assert!(sound.ses.is_empty());
+        assert!(!sound.is_voice_playing());
+        assert!(!sound.ducked);
+    }
+
+    #[test]
+    fn held_null_chunks_play_until_stopped() {
+        let mut sound = SoundDirector::new();
+        sound.backend = Box::new(NullBackend::holding_channels());
+        let se = sound.play_se("se.wav", SEOption::default());
+        sound.clean_se();
+        assert_eq!(sound.ses.len(), 1);
+        sound.stop_se(se);
+        assert!(sound.ses.is_empty());
+    }
+
+    fn held_sound() -> SoundDirector<'static> {
+        let mut sound = SoundDirector::new();
+        sound.backend = Box::new(NullBackend::holding_channels());
+        sound
+    }
+
+    fn playing_paths(sound: &SoundDirector) -> Vec<String> {
+        sound.ses.iter().map(|se| se.path.clone()).collect()
+    }
+
+    #[test]
+    fn full_pool_replaces_the_lowest_priority_se() {
+        let mut sound = held_sound();
+        sound.set_max_se_channels(2);
+        sound.play_se("low.wav", SEOption { priority: 0, ..Default::default() });
+        sound.play_se("high.wav", SEOption { priority: 5, ..Default::default() });
+        sound.play_se("mid.wav", SEOption { priority: 3, ..Default::default() });
+        assert_eq!(playing_paths(&sound), vec!["high.wav".to_owned(), "mid.wav".to_owned()]);
+        sound.play_se("lower.wav", SEOption { priority: 1, ..Default::default() });
+        assert_eq!(playing_paths(&sound), vec!["high.wav".to_owned(), "mid.wav".to_owned()]);
+    }
+
+    #[test]
+    fn shrinking_the_pool_stops_the_lowest_priority_ses() {
+        let mut sound = held_sound();
+        sound.play_se("a.wav", SEOption { priority: 2, ..Default::default() });
+        sound.play_se("b.wav", SEOption { priority: 0, ..Default::default() });
+        sound.play_se("c.wav", SEOption { priority: 1, ..Default::default() });
+        sound.set_max_se_channels(1);
+        assert_eq!(playing_paths(&sound), vec!["a.wav".to_owned()]);
+    }
+
+    #[test]
+    fn max_instances_replaces_the_oldest_instance() {
+        let mut sound = held_sound();
+        let option = SEOption { max_instances: Some(2), ..Default::default() };
+        let first = sound.play_se("step.wav", option.clone());
+        sound.play_se("step.wav", option.clone());
+        sound.play_se("step.wav", option.clone());
+        assert_eq!(sound.ses.len(), 2);
+        assert!(sound.ses.iter().all(|se| se.id != first.id()));
+        sound.play_se("mute.wav", SEOption { max_instances: Some(0), ..Default::default() });
+        assert_eq!(sound.ses.len(), 2);
+    }
+
+    #[test]
+    fn paused_ses_survive_clean() {
+        let mut sound = SoundDirector::new();
+        sound.play_se("se.wav", SEOption::default());
+        sound.pause_all();
+        sound.clean_se();
+        assert_eq!(sound.ses.len(), 1);
+        sound.resume_all();
+        sound.clean_se();
+        assert!(sound.ses.is_empty());
+    }
+
+    #[test]
+    fn loop_start_without_loop_end_skips_the_intro() {
+        let mut sound = SoundDirector::new();
+        sound.play_music("bgm.ogg", MusicOption { loops: 1, loop_start: Some(2.0), ..Default::default() });
+        sound.backend.stop_music(0);
+        sound.take_audio_records();
+        sound.update_music();
+        assert_eq!(sound.take_audio_records(), vec!["play_music bgm.ogg loops 1 fade_in 0 from 2".to_owned()]);
+        assert!(sound.get_music_position().unwrap() >= 2.0);
+        sound.backend.stop_music(0);
+        sound.update_music();
+        assert_eq!(sound.get_current_music(), None);
+    }
+
+    #[test]
+    fn crossfade_overlaps_the_outgoing_track() {
+        let mut sound = SoundDirector::new();
+        sound.play_music("a.ogg", MusicOption::default());
+        sound.take_audio_records();
+        sound.play_music("b.ogg", MusicOption { crossfade: Some(500), ..Default::default() });
+        let records = sound.take_audio_records();
+        assert!(records[0].starts_with("play_music_tail a.ogg from "));
+        assert!(records[0].ends_with(" fade_out 500"));
+        assert_eq!(records.last().unwrap(), "play_music b.ogg loops 0 fade_in 500 from 0");
+        assert_eq!(sound.get_current_music(), Some("b.ogg".to_owned()));
     }
 
-}
\ No newline at end of file
+}
@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+use ::director::sound::AudioBackend;
+use ::util::parameter::{ SoundBus };
+
+pub struct NullBackend {
+    music_playing: bool,
+    next_channel: i32,
+    channels: HashMap<i32, bool>,
+    hold_channels: bool,
+    records: Vec<String>
+}
+
+impl NullBackend {
+
+    pub fn new() -> Self {
+        Self {
+            music_playing: false,
+            next_channel: 0,
+            channels: HashMap::new(),
+            hold_channels: false,
+            records: Vec::new()
+        }
+    }
+
+    #[cfg(test)]
+    pub fn holding_channels() -> Self {
+        Self { hold_channels: true, ..Self::new() }
+    }
+
+    fn record(&mut self, call: String) {
+        self.records.push(call);
+    }
+
+}
+
+impl AudioBackend for NullBackend {
+
+    fn add_alias(&mut self, _name: &str, _path: &str) { }
+
+    fn play_music(&mut self, path: &str, loops: i32, fade_in: i32, position: f64) {
+        self.music_playing = true;
+        self.record(format!("play_music {} loops {} fade_in {} from {}", path, loops, fade_in, position));
+    }
+
+    fn stop_music(&mut self, fade_out: i32) {
+        self.music_playing = false;
+        self.record(format!("stop_music fade_out {}", fade_out));
+    }
+
+    fn play_music_tail(&mut self, path: &str, position: f64, fade_out: i32) {
+        self.record(format!("play_music_tail {} from {} fade_out {}", path, position, fade_out));
+    }
+
+    fn pause_music(&mut self) {
+        self.record("pause_music".to_owned());
+    }
+
+    fn resume_music(&mut self) {
+        self.record("resume_music".to_owned());
+    }
+
+    fn is_music_playing(&self) -> bool {
+        self.music_playing
+    }
+
+    fn set_music_position(&mut self, position: f64) {
+        self.record(format!("set_music_position {}", position));
+    }
+
+    fn set_music_volume(&mut self, volume: f32) {
+        self.record(format!("set_music_volume {}", volume));
+    }
+
+    fn allocate_channels(&mut self, channels: i32) {
+        self.record(format!("allocate_channels {}", channels));
+    }
+
+    fn play_chunk(&mut self, path: &str, bus: SoundBus, volume: f32) -> i32 {
+        let call = match bus {
+            SoundBus::Voice => "play_voice",
+            _ => "play_se"
+        };
+        self.record(format!("{} {} at volume {}", call, path, volume));
+        self.next_channel += 1;
+        self.channels.insert(self.next_channel, false);
+        self.next_channel
+    }
+
+    fn stop_channel(&mut self, channel: i32) {
+        self.channels.remove(&channel);
+        self.record(format!("stop_channel {}", channel));
+    }
+
+    fn set_channel_volume(&mut self, channel: i32, volume: f32) {
+        self.record(format!("set_channel_volume {} {}", channel, volume));
+    }
+
+    fn is_channel_playing(&self, channel: i32) -> bool {
+        self.channels.contains_key(&channel)
+    }
+
+    fn is_channel_paused(&self, channel: i32) -> bool {
+        self.channels.get(&channel).cloned().unwrap_or(false)
+    }
+
+    fn pause_channel(&mut self, channel: i32) {
+        if let Some(paused) = self.channels.get_mut(&channel) { *paused = true; }
+        self.record(format!("pause_channel {}", channel));
+    }
+
+    fn resume_channel(&mut self, channel: i32) {
+        if let Some(paused) = self.channels.get_mut(&channel) { *paused = false; }
+        self.record(format!("resume_channel {}", channel));
+    }
+
+    fn clean_channels(&mut self) {
+        if self.hold_channels { return; }
+        self.channels.retain(|_, paused| *paused);
+    }
+
+    fn take_records(&mut self) -> Vec<String> {
+        self.records.drain(..).collect()
+    }
+
+}
@@ -6,11 +6,14 @@ extern crate base64;
 extern crate rusqlite;
 extern crate uuid;
 extern crate serde;
+#[macro_use]
+extern crate serde_derive;
 extern crate serde_json;
 extern crate rand;
 extern crate backtrace;
 extern crate html5ever;
 extern crate chrono;
+extern crate image;
 
 pub mod application;
 pub mod director;
@@ -7,7 +7,7 @@ use std::any::Any;
 use ::node::{ NodeChild, NodeDelegate, NodeId, NodeLike, AddChildOption, ConflictType, RunActionOption };
 use ::action::{ ActionLike, ActionStatus };
 use ::util::{ director, get_mouse_position };
-use ::util::parameter::{ Point, AnchorPoint, Size, Rect, Circle, Color, Opacity, Scale, Rotation };
+use ::util::parameter::{ Point, AnchorPoint, Size, Rect, Circle, Color, Opacity, Scale, Rotation, PointerEvent, PointerEventType };
 use ::resource::{ Texture, Font, ResourceKey };
 
 pub struct Node<T> where T: NodeDelegate + Any {
@@ -108,15 +108,15 @@ impl <T> NodeLike for Node<T> where T: NodeDelegate + Any {
         director::prepare_render_tree(self.node());
     }
 
-    fn inner_render(&self) {
+    fn inner_render(&self, alpha: f32) {
         self.inner_prepare_render_tree();
-        self.delegate.render();
-        self.inner_render_children();
+        self.delegate.render(alpha);
+        self.inner_render_children(alpha);
     }
 
-    fn inner_render_children(&self) {
+    fn inner_render_children(&self, alpha: f32) {
         for child in self.inner_get_children() {
-            child.inner_render();
+            child.inner_render(alpha);
         }
     }
 
@@ -309,6 +309,17 @@ impl <T> NodeLike for Node<T> where T: NodeDelegate + Any {
         }
     }
 
+    fn inner_dispatch_pointer_event(&self, event: &PointerEvent) {
+        match event.get_event_type() {
+            PointerEventType::Enter => { self.delegate.on_pointer_enter(event); },
+            PointerEventType::Leave => { self.delegate.on_pointer_leave(event); },
+            PointerEventType::Click => { self.delegate.on_click(event); },
+            PointerEventType::DragStart => { self.delegate.on_drag_start(event); },
+            PointerEventType::Drag => { self.delegate.on_drag(event); },
+            PointerEventType::Drop => { self.delegate.on_drop(event); }
+        }
+    }
+
     fn inner_set_conflict_type(&self, conflict_type: ConflictType) {
         self.conflict_type.replace(conflict_type);
     }
@@ -4,7 +4,7 @@ use ::resource::{ Texture, Font };
 use ::node::{ Node, NodeId, NodeLike, AddChildOption, ConflictType, RunActionOption };
 use ::action::{ ActionLike };
 use ::util::{ director };
-use ::util::parameter::{ Point, AnchorPoint, Size, Color, Scale, Opacity, Rotation };
+use ::util::parameter::{ Point, AnchorPoint, Size, Color, Scale, Opacity, Rotation, PointerEvent };
 
 pub trait NodeDelegate: Any {
 
@@ -12,10 +12,22 @@ pub trait NodeDelegate: Any {
 
     fn update(&self);
 
-    fn render(&self) { }
+    fn render(&self, _alpha: f32) { }
 
     fn before_add_child(&self, child: Rc<dyn NodeLike>) { }
 
+    fn on_pointer_enter(&self, event: &PointerEvent) { }
+
+    fn on_pointer_leave(&self, event: &PointerEvent) { }
+
+    fn on_click(&self, event: &PointerEvent) { }
+
+    fn on_drag_start(&self, event: &PointerEvent) { }
+
+    fn on_drag(&self, event: &PointerEvent) { }
+
+    fn on_drop(&self, event: &PointerEvent) { }
+
     fn before_be_added_child(&self, parent: Rc<dyn NodeLike>){ }
 
     fn use_cache(&self) -> bool {
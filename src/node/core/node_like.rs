@@ -2,7 +2,7 @@ use std::rc::Rc;
 use ::node::{ NodeId, AddChildOption, ConflictType, RunActionOption };
 use ::resource::{ Texture, Font, ResourceKey };
 use ::action::{ ActionLike };
-use ::util::parameter::{ Point, AnchorPoint, Size, Rect, Color, Opacity, Scale, Rotation };
+use ::util::parameter::{ Point, AnchorPoint, Size, Rect, Color, Opacity, Scale, Rotation, PointerEvent };
 
 pub trait NodeLike {
 
@@ -22,9 +22,9 @@ pub trait NodeLike {
 
     fn inner_update_children(&self);
 
-    fn inner_render(&self);
+    fn inner_render(&self, alpha: f32);
 
-    fn inner_render_children(&self);
+    fn inner_render_children(&self, alpha: f32);
 
     fn inner_use_cache(&self) -> bool;
 
@@ -92,6 +92,8 @@ pub trait NodeLike {
 
     fn inner_is_mouse_hover(&self) -> bool;
 
+    fn inner_dispatch_pointer_event(&self, event: &PointerEvent);
+
     fn inner_set_conflict_type(&self, conflict_type: ConflictType);
 
     fn inner_get_conflict_type(&self) -> ConflictType;
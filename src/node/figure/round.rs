@@ -38,7 +38,7 @@ impl NodeDelegate for Round {
 
     fn update(&self) { }
 
-    fn render(&self) {
+    fn render(&self, _alpha: f32) {
         self.render_round(&self.option.borrow().color);
     }
 
@@ -36,7 +36,7 @@ impl NodeDelegate for Square {
 
     fn update(&self) { }
 
-    fn render(&self) {
+    fn render(&self, _alpha: f32) {
         self.render_square(&self.option.borrow().color);
     }
 
@@ -32,7 +32,7 @@ impl NodeDelegate for Image {
 
     fn update(&self) { }
 
-    fn render(&self) {
+    fn render(&self, _alpha: f32) {
         self.render_texture(self.image.clone());
     }
 
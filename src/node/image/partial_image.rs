@@ -56,7 +56,7 @@ impl NodeDelegate for PartialImage {
 
     fn update(&self) { }
 
-    fn render(&self) { }
+    fn render(&self, _alpha: f32) { }
 
 }
 
@@ -128,7 +128,7 @@ impl NodeDelegate for Label {
 
     fn update(&self) { }
 
-    fn render(&self) {
+    fn render(&self, _alpha: f32) {
     }
 
 }
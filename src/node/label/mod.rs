@@ -3,10 +3,12 @@ mod label_option;
 mod one_line_label;
 mod one_line_label_option;
 mod pretty_label;
+mod text_field;
 
 pub use self::label::*;
 pub use self::label_option::*;
 pub use self::one_line_label::*;
 pub use self::one_line_label_option::*;
 pub use self::pretty_label::*;
+pub use self::text_field::*;
 
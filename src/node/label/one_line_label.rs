@@ -78,7 +78,7 @@ impl NodeDelegate for OneLineLabel {
 
     fn update(&self) { }
 
-    fn render(&self) {
+    fn render(&self, _alpha: f32) {
         let text = self.text.borrow().clone();
         let font = self.font.borrow().clone();
         let option = self.option.borrow().clone();
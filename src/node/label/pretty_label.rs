@@ -141,7 +141,7 @@ impl NodeDelegate for PrettyLabel {
 
     fn update(&self) { }
 
-    fn render(&self) { }
+    fn render(&self, _alpha: f32) { }
 
 }
 
@@ -0,0 +1,171 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::cmp::max;
+use ::node::{ Node, NodeDelegate, NodeLike, AddChildOption };
+use ::node::label::{ OneLineLabel, OneLineLabelOption };
+use ::node::figure::{ Square, SquareOption };
+use ::util::{ director };
+use ::util::parameter::{ Size, Point, AnchorPoint, FontStyle };
+use ::resource::{ Font };
+
+pub struct TextField {
+    size: RefCell<Option<Size>>,
+    text: RefCell<String>,
+    composition: RefCell<String>,
+    cursor: RefCell<i32>,
+    max_length: RefCell<Option<usize>>,
+    focused: RefCell<bool>,
+    font: RefCell<Rc<Font>>
+}
+
+impl TextField {
+
+    pub fn create<A, B>(text: A, option: B) -> Rc<Node<Self>>
+    where
+        A: Into<String>,
+        B: Into<OneLineLabelOption>
+    {
+        let o = option.into();
+        let font = director::load_font(&o);
+        let t = text.into();
+        let n = Node::create(Self {
+            size: RefCell::new(None),
+            text: RefCell::new(t.clone()),
+            composition: RefCell::new(String::new()),
+            cursor: RefCell::new(0),
+            max_length: RefCell::new(None),
+            focused: RefCell::new(false),
+            font: RefCell::new(font)
+        });
+        let composition_option = OneLineLabelOption {
+            style: FontStyle::underline(),
+            ..o.clone()
+        };
+        let text_label = OneLineLabel::create(t.clone(), &o);
+        let height = text_label.get_size().height();
+        n.size.replace(Some(Size::new(n.measure_width(&t) + 1, height)));
+        n.add_child(text_label, "text");
+        n.add_child(OneLineLabel::create("", composition_option), "composition");
+        n.add_child(Square::create((1, height), SquareOption { color: o.color.clone() }), AddChildOption {
+            z_index: 1,
+            name: Some("caret".to_owned())
+        });
+        n.build();
+        n
+    }
+
+    pub fn get_text(&self) -> String {
+        self.text.borrow().clone()
+    }
+
+    pub fn set_text<A>(&self, text: A)
+    where A: Into<String>
+    {
+        let t = self.truncate(text.into());
+        self.text.replace(t);
+        self.updated();
+    }
+
+    pub fn get_composition(&self) -> String {
+        self.composition.borrow().clone()
+    }
+
+    pub fn set_max_length(&self, max_length: Option<usize>) {
+        self.max_length.replace(max_length);
+        let t = self.text.borrow().clone();
+        self.set_text(t);
+    }
+
+    pub fn is_focused(&self) -> bool {
+        *self.focused.borrow()
+    }
+
+    pub fn focus(&self) {
+        self.focused.replace(true);
+        director::start_text_input(self.node().inner_get_absolute_render_rect());
+        director::take_text_input();
+        self.updated();
+    }
+
+    pub fn blur(&self) {
+        self.focused.replace(false);
+        self.composition.replace(String::new());
+        self.cursor.replace(0);
+        director::stop_text_input();
+        self.updated();
+    }
+
+    fn truncate(&self, text: String) -> String {
+        match *self.max_length.borrow() {
+            Some(max_length) => text.chars().take(max_length).collect(),
+            None => text
+        }
+    }
+
+    fn measure_width(&self, text: &str) -> u32 {
+        if text.is_empty() { return 0; }
+        director::measure_label_size(text, self.font.borrow().clone()).width()
+    }
+
+    fn build(&self) {
+        let text = self.text.borrow().clone();
+        let composition = self.composition.borrow().clone();
+        let cursor = *self.cursor.borrow();
+        let text_label: Rc<Node<OneLineLabel>> = self.get_child("text").unwrap();
+        let composition_label: Rc<Node<OneLineLabel>> = self.get_child("composition").unwrap();
+        let caret: Rc<Node<Square>> = self.get_child("caret").unwrap();
+        text_label.set_text(text.as_str());
+        text_label.set_anchor_point(AnchorPoint::new(0.0, 0.0));
+        text_label.set_position(Point::new(0, 0));
+        let text_width = self.measure_width(&text);
+        composition_label.set_text(composition.as_str());
+        composition_label.set_anchor_point(AnchorPoint::new(0.0, 0.0));
+        composition_label.set_position(Point::new(text_width as i32, 0));
+        composition_label.set_visible(!composition.is_empty());
+        let composition_head: String = composition.chars().take(max(cursor, 0) as usize).collect();
+        let caret_x = text_width + self.measure_width(&composition_head);
+        let height = text_label.get_size().height();
+        caret.set_anchor_point(AnchorPoint::new(0.0, 0.0));
+        caret.set_position(Point::new(caret_x as i32, 0));
+        caret.set_visible(*self.focused.borrow());
+        let width = max(text_width + self.measure_width(&composition), caret_x + 1);
+        self.size.replace(Some(Size::new(width, height)));
+    }
+
+    fn updated(&self) {
+        self.build();
+        self.clear_cache();
+    }
+
+}
+
+impl NodeDelegate for TextField {
+
+    fn get_size(&self) -> Size {
+        self.size.borrow().clone().unwrap()
+    }
+
+    fn use_cache(&self) -> bool {
+        true
+    }
+
+    fn update(&self) {
+        if !self.is_focused() { return; }
+        let info = director::take_text_input();
+        let mut text = self.text.borrow().clone();
+        for _ in 0..info.get_backspaces() {
+            text.pop();
+        }
+        text.push_str(&info.get_committed());
+        let text = self.truncate(text);
+        let changed = text != *self.text.borrow() ||
+            info.get_composition() != *self.composition.borrow() ||
+            info.get_cursor() != *self.cursor.borrow();
+        if !changed { return; }
+        self.text.replace(text);
+        self.composition.replace(info.get_composition());
+        self.cursor.replace(info.get_cursor());
+        self.updated();
+    }
+
+}
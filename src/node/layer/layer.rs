@@ -42,7 +42,7 @@ impl NodeDelegate for Layer {
 
     fn update(&self) { }
 
-    fn render(&self) { }
+    fn render(&self, _alpha: f32) { }
 
 }
 
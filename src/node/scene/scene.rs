@@ -9,7 +9,7 @@ pub trait Scene: NodeDelegate {
 
     fn update_scene(&self) {}
 
-    fn render_scene(&self) {}
+    fn render_scene(&self, _alpha: f32) {}
 
 }
 
@@ -17,7 +17,7 @@ pub trait SceneLike: NodeLike {
 
     fn start_update(&self);
 
-    fn start_render(&self);
+    fn start_render(&self, alpha: f32);
 
 }
 
@@ -29,7 +29,7 @@ impl <T> NodeDelegate for T where T: Scene {
 
     fn update(&self) { }
 
-    fn render(&self) { }
+    fn render(&self, _alpha: f32) { }
 
     fn get_fixed_anchor_point(&self) -> Option<AnchorPoint> {
         Some(AnchorPoint::new(0.0, 0.0))
@@ -55,10 +55,10 @@ impl <T> SceneLike for Node<T> where T: Scene + Any {
         self.inner_update_children();
     }
 
-    fn start_render(&self) {
+    fn start_render(&self, alpha: f32) {
         self.node().inner_prepare_render_tree();
-        self.render_scene();
-        self.inner_render_children();
+        self.render_scene(alpha);
+        self.inner_render_children(alpha);
     }
 
 }
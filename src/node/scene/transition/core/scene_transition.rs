@@ -1,16 +1,15 @@
 use std::rc::Rc;
 use std::cell::RefCell;
 use ::node::scene::transition::{ SceneTransitionDelegate, TransitionStatus };
-use ::util::{with_context};
+use ::util::{ with_context, director };
 use ::util::parameter::{ Size };
 use ::util::easing::{ EasingFunction };
 use sdl2::render::{ Texture };
-use time::{ Tm };
 
 pub struct SceneTransition {
     delegate: Rc<dyn SceneTransitionDelegate>,
     status: RefCell<TransitionStatus>,
-    start: RefCell<Option<Tm>>,
+    start: RefCell<Option<i64>>,
     duration: Option<f32>,
     easing: Option<Rc<dyn EasingFunction>>
 }
@@ -51,7 +50,7 @@ impl SceneTransition {
         if let Some(duration) = self.duration {
             if duration == 0.0 { return 1.0; }
             let start = self.start.borrow().clone().unwrap();
-            let d = (time::now() - start).num_microseconds().unwrap();
+            let d = director::get_clock_time() - start;
             let progress = d as f32 / (duration * 1_000_000.0);
             if progress > 1.0 { return 1.0; }
             if let Some(easing) = self.easing.clone() { return easing.ease(progress); }
@@ -69,7 +68,7 @@ impl SceneTransition {
         let status = self.status.borrow().clone();
         match status {
             TransitionStatus::Wait => {
-                self.start.replace(Some(time::now()));
+                self.start.replace(Some(director::get_clock_time()));
                 self.status.replace(TransitionStatus::Processing);
                 true
             },
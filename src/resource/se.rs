@@ -2,17 +2,17 @@ use ::resource::{ ResourceKey };
 
 pub struct SE {
     key: ResourceKey,
-    channel: String
+    id: u64
 }
 
 impl SE {
 
-    pub fn new<A>(key: A, channel: String) -> Self
+    pub fn new<A>(key: A, id: u64) -> Self
     where A: Into<ResourceKey>
     {
         Self {
             key: key.into(),
-            channel: channel
+            id: id
         }
     }
 
@@ -20,8 +20,8 @@ impl SE {
         self.key.clone()
     }
 
-    pub fn channel(&self) -> String {
-        self.channel.clone()
+    pub fn id(&self) -> u64 {
+        self.id
     }
 
 }
@@ -1,20 +1,23 @@
 use std::rc::Rc;
 use std::any::Any;
 use ::director::{ Director };
-use ::application::{ Application };
+use ::application::{ Application, WindowMode };
 use ::node::{ NodeLike, Node, NodeDelegate, NodeId };
 use ::node::scene::{ SceneLike };
 use ::node::scene::transition::{ SceneTransition, TransitionStatus };
 use ::node::label::{ LabelOption, OneLineLabelOption };
 use ::resource::{ ResourceKey, SE, Font, Texture };
-use ::util::{ SaveMigrator };
-use ::util::parameter::{ Size, Color, Point, InputInfo, InputCode, MusicOption };
+use ::util::{ SaveMigrator, RandSnapshot };
+use ::util::parameter::{ Size, Color, Point, InputInfo, InputCode, InputCodeMap, InputRecording, PressState, Rect, TextInputInfo, TouchPoint, TouchGesture, MusicOption, SEOption, SoundBus };
 use rand::distributions::{ Standard, Distribution };
+use image::{ RgbaImage };
 use sdl2::{ EventPump };
 use serde::de::DeserializeOwned;
 use serde::ser::Serialize;
 use serde_json::{ Value };
 
+pub use ::director::{ CONFIG_WINDOW_MODE, CONFIG_INPUT_CODE_MAP, CONFIG_VOLUME_MASTER, CONFIG_VOLUME_BGM, CONFIG_VOLUME_SE, CONFIG_VOLUME_VOICE };
+
 pub(crate) fn with_director<T, R>(callback: T) -> R where T: FnOnce(&Director) -> R {
     ::DIRECTOR.with(|d| {
         callback(d)
@@ -25,6 +28,56 @@ pub fn window_size() -> Size {
     with_director(|d| d.window_size())
 }
 
+pub fn get_config<T>(key: &str) -> Option<T> where T: DeserializeOwned {
+    with_director(|d| d.get_config(key))
+}
+
+pub fn set_config<T>(key: &str, value: &T) -> Result<(), String> where T: Serialize {
+    with_director(|d| d.set_config(key, value))
+}
+
+pub fn flush_config() {
+    with_director(|d| d.flush_config());
+}
+
+pub fn reset_config(key: &str) -> Result<(), String> {
+    with_director(|d| d.reset_config(key))
+}
+
+pub(crate) fn apply_all_config() {
+    with_director(|d| d.apply_all_config());
+}
+
+pub fn is_background() -> bool {
+    with_director(|d| d.is_background())
+}
+
+pub fn is_paused() -> bool {
+    with_director(|d| d.is_paused())
+}
+
+pub(crate) fn is_suspended() -> bool {
+    with_director(|d| d.is_suspended())
+}
+
+pub fn set_paused(paused: bool) {
+    with_director(|d| d.set_paused(paused));
+}
+
+pub fn get_window_mode() -> WindowMode {
+    with_director(|d| d.get_window_mode())
+}
+
+pub fn set_window_mode(window_mode: WindowMode) -> Result<(), String> {
+    with_director(|d| d.set_window_mode(window_mode))
+}
+
+pub fn set_window_size<A>(size: A) -> Result<(), String>
+    where A: Into<Size>
+{
+    with_director(|d| d.set_window_size(size.into()))
+}
+
 pub fn get_resolution_size() -> Size {
     with_director(|d| d.get_resolution_size())
 }
@@ -33,6 +86,32 @@ pub fn rand<T>() -> T where Standard: Distribution<T> {
     with_director(|d| d.rand())
 }
 
+pub fn rand_with<T, A>(stream: A) -> T
+    where Standard: Distribution<T>, A: Into<String>
+{
+    with_director(|d| d.rand_with(stream))
+}
+
+pub fn seed_rand(seed: u64) {
+    with_director(|d| d.seed_rand(seed));
+}
+
+pub fn seed_rand_stream<A>(stream: A, seed: u64) where A: Into<String> {
+    with_director(|d| d.seed_rand_stream(stream, seed));
+}
+
+pub fn get_rand_seed() -> u64 {
+    with_director(|d| d.get_rand_seed())
+}
+
+pub fn snapshot_rand() -> RandSnapshot {
+    with_director(|d| d.snapshot_rand())
+}
+
+pub fn restore_rand(snapshot: &RandSnapshot) {
+    with_director(|d| d.restore_rand(snapshot));
+}
+
 pub(crate) fn is_continuing() -> bool {
     with_director(|d| d.is_continuing())
 }
@@ -79,10 +158,39 @@ pub(crate) fn set_application(application: Rc<dyn Application>) {
     with_director(|d| d.set_application(application));
 }
 
+pub(crate) fn reset() {
+    with_director(|d| d.reset());
+}
+
 pub(crate) fn set_current_fps(fps: usize) {
     with_director(|d| d.set_current_fps(fps));
 }
 
+pub fn get_clock_time() -> i64 {
+    with_director(|d| d.get_clock_time())
+}
+
+pub fn get_frame_delta() -> i64 {
+    with_director(|d| d.get_frame_delta())
+}
+
+pub(crate) fn tick_clock(delta: i64) {
+    with_director(|d| d.tick_clock(delta));
+}
+
+pub fn advance_clock(milliseconds: f64) {
+    let delta = (milliseconds * 1_000.0).round() as i64;
+    with_director(|d| d.advance_clock(delta));
+}
+
+pub fn is_manual_clock() -> bool {
+    with_director(|d| d.is_manual_clock())
+}
+
+pub fn set_manual_clock(manual: bool) {
+    with_director(|d| d.set_manual_clock(manual));
+}
+
 pub fn default_label_option() -> Option<LabelOption> {
     with_director(|d| d.default_label_option())
 }
@@ -179,6 +287,14 @@ pub(crate) fn render_canvas(scene: Rc<dyn SceneLike>, prev_scene: Option<Rc<dyn
     with_director(|d| d.render_canvas(scene, prev_scene, transition))
 }
 
+pub fn capture_screen() -> Option<RgbaImage> {
+    with_director(|d| d.capture_screen())
+}
+
+pub fn capture_node(node: Rc<dyn NodeLike>) -> Option<RgbaImage> {
+    with_director(|d| d.capture_node(node))
+}
+
 pub(crate) fn destroy_render_cache(key: &ResourceKey) {
     with_director(|d| d.destroy_render_cache(key));
 }
@@ -193,8 +309,130 @@ pub fn get_input_info<A>(key: A) -> InputInfo
     with_director(|d| d.get_input_info(key))
 }
 
-pub(crate) fn update_input_state(event_pump: &mut EventPump) {
-    with_director(|d| d.update_input_state(event_pump));
+pub fn get_input_info_in<A>(context: Option<&str>, key: A) -> InputInfo
+    where A: Into<String>
+{
+    with_director(|d| d.get_input_info_in(context, key))
+}
+
+pub(crate) fn poll_input(event_pump: &mut EventPump) {
+    with_director(|d| d.poll_input(event_pump));
+}
+
+pub(crate) fn update_input_state() {
+    with_director(|d| d.update_input_state());
+}
+
+pub fn get_mouse_motion() -> Point {
+    with_director(|d| d.get_mouse_motion())
+}
+
+pub fn get_mouse_wheel_delta() -> Point {
+    with_director(|d| d.get_mouse_wheel_delta())
+}
+
+pub fn get_touches() -> Vec<TouchPoint> {
+    with_director(|d| d.get_touches())
+}
+
+pub fn get_touch_gestures() -> Vec<TouchGesture> {
+    with_director(|d| d.get_touch_gestures())
+}
+
+pub(crate) fn dispatch_pointer_events() {
+    with_director(|d| d.dispatch_pointer_events());
+}
+
+pub fn set_key_repeat(delay: f64, interval: f64) {
+    let (delay_time, interval_time) = ((delay * 1_000.0).round() as i64, (interval * 1_000.0).round() as i64);
+    with_director(|d| d.set_key_repeat(delay_time, interval_time));
+}
+
+pub fn is_input_buffered<A>(context: Option<&str>, key: A, steps: u32) -> bool
+    where A: Into<String>
+{
+    with_director(|d| d.is_input_buffered(context, key, steps))
+}
+
+pub fn push_input_context<A>(name: A, pass_through: bool)
+    where A: Into<String>
+{
+    with_director(|d| d.push_input_context(name, pass_through));
+}
+
+pub fn pop_input_context<A>(name: A)
+    where A: Into<String>
+{
+    with_director(|d| d.pop_input_context(name));
+}
+
+pub fn consume_input_buffer<A>(key: A)
+    where A: Into<String>
+{
+    with_director(|d| d.consume_input_buffer(key));
+}
+
+pub fn start_text_input<A>(rect: A)
+    where A: Into<Rect>
+{
+    with_director(|d| d.start_text_input(rect.into()));
+}
+
+pub fn stop_text_input() {
+    with_director(|d| d.stop_text_input());
+}
+
+pub fn is_text_input_active() -> bool {
+    with_director(|d| d.is_text_input_active())
+}
+
+pub fn take_text_input() -> TextInputInfo {
+    with_director(|d| d.take_text_input())
+}
+
+pub fn inject_input(code: InputCode, state: PressState) -> Result<(), String> {
+    with_director(|d| d.inject_input(code, state))
+}
+
+pub fn inject_mouse_motion<A>(point: A) where A: Into<Point> {
+    let p = point.into();
+    with_director(|d| d.inject_mouse_motion(p));
+}
+
+pub fn get_stick_deadzone() -> f32 {
+    with_director(|d| d.get_stick_deadzone())
+}
+
+pub fn set_stick_deadzone(deadzone: f32) {
+    with_director(|d| d.set_stick_deadzone(deadzone));
+}
+
+pub fn get_controller_players() -> Vec<u32> {
+    with_director(|d| d.get_controller_players())
+}
+
+pub fn start_input_recording() {
+    with_director(|d| d.start_input_recording());
+}
+
+pub fn stop_input_recording() -> Option<InputRecording> {
+    with_director(|d| d.stop_input_recording())
+}
+
+pub fn is_input_recording() -> bool {
+    with_director(|d| d.is_input_recording())
+}
+
+pub fn start_input_replay(recording: InputRecording) {
+    with_director(|d| d.start_input_replay(recording));
+}
+
+pub fn stop_input_replay() {
+    with_director(|d| d.stop_input_replay());
+}
+
+pub fn is_input_replaying() -> bool {
+    with_director(|d| d.is_input_replaying())
 }
 
 pub fn add_key_code<A>(key: A, code: InputCode)
@@ -215,6 +453,48 @@ pub fn reset_key_code<A>(key: Option<A>)
     with_director(|d| d.reset_key_code(key));
 }
 
+pub fn remove_key_code<A>(key: A, code: &InputCode)
+    where A: Into<String>
+{
+    with_director(|d| d.remove_key_code(key, code));
+}
+
+pub fn get_input_code_map() -> InputCodeMap {
+    with_director(|d| d.get_input_code_map())
+}
+
+pub fn set_input_code_map(input_code_map: InputCodeMap) {
+    with_director(|d| d.set_input_code_map(input_code_map));
+}
+
+pub fn load_input_code_map<A>(name: A) -> Result<(), String>
+    where A: Into<String>
+{
+    with_director(|d| d.load_input_code_map(name))
+}
+
+pub fn save_input_code_map<A>(name: A) -> Result<(), String>
+    where A: Into<String>
+{
+    with_director(|d| d.save_input_code_map(name))
+}
+
+pub fn start_input_capture() {
+    with_director(|d| d.start_input_capture());
+}
+
+pub fn cancel_input_capture() {
+    with_director(|d| d.cancel_input_capture());
+}
+
+pub fn is_input_capturing() -> bool {
+    with_director(|d| d.is_input_capturing())
+}
+
+pub fn take_captured_input() -> Option<InputCode> {
+    with_director(|d| d.take_captured_input())
+}
+
 pub(crate) fn is_quit() -> bool {
     with_director(|d| d.is_quit())
 }
@@ -223,16 +503,54 @@ pub fn reset_is_quit() {
     with_director(|d| d.reset_is_quit())
 }
 
+pub(crate) fn initialize_audio() {
+    with_director(|d| d.initialize_audio());
+}
+
+pub fn take_audio_records() -> Vec<String> {
+    with_director(|d| d.take_audio_records())
+}
+
 pub fn play_music<A, B>(path: A, option: B)
     where A: Into<String>, B: Into<MusicOption>
 {
     with_director(|d| d.play_music(path, option));
 }
 
+pub fn queue_music<A, B>(path: A, option: B)
+    where A: Into<String>, B: Into<MusicOption>
+{
+    with_director(|d| d.queue_music(path, option));
+}
+
+pub fn clear_music_queue() {
+    with_director(|d| d.clear_music_queue());
+}
+
 pub fn stop_music(fade_out: i32) {
     with_director(|d| d.stop_music(fade_out));
 }
 
+pub fn pause_music() {
+    with_director(|d| d.pause_music());
+}
+
+pub fn resume_music() {
+    with_director(|d| d.resume_music());
+}
+
+pub fn is_music_paused() -> bool {
+    with_director(|d| d.is_music_paused())
+}
+
+pub fn get_current_music() -> Option<String> {
+    with_director(|d| d.get_current_music())
+}
+
+pub fn get_music_position() -> Option<f64> {
+    with_director(|d| d.get_music_position())
+}
+
 pub fn stop_se(se: Rc<SE>) {
     with_director(|d| d.stop_se(se));
 }
@@ -241,16 +559,70 @@ pub fn stop_all_se() {
     with_director(|d| d.stop_all_se());
 }
 
-pub fn play_se<A>(path: A) -> Rc<SE>
-    where A: Into<String>
+pub fn play_se<A, B>(path: A, option: B) -> Rc<SE>
+    where A: Into<String>, B: Into<SEOption>
+{
+    with_director(|d| d.play_se(path, option))
+}
+
+pub fn play_voice<A, B>(path: A, option: B) -> Rc<SE>
+    where A: Into<String>, B: Into<SEOption>
 {
-    with_director(|d| d.play_se(path))
+    with_director(|d| d.play_voice(path, option))
+}
+
+pub fn stop_voice() {
+    with_director(|d| d.stop_voice());
+}
+
+pub fn is_voice_playing() -> bool {
+    with_director(|d| d.is_voice_playing())
+}
+
+pub fn get_voice_ducking() -> f32 {
+    with_director(|d| d.get_voice_ducking())
+}
+
+pub fn set_voice_ducking(ducking: f32) {
+    with_director(|d| d.set_voice_ducking(ducking));
+}
+
+pub fn get_max_se_channels() -> usize {
+    with_director(|d| d.get_max_se_channels())
+}
+
+pub fn set_max_se_channels(channels: usize) {
+    with_director(|d| d.set_max_se_channels(channels));
+}
+
+pub fn get_volume(bus: SoundBus) -> f32 {
+    with_director(|d| d.get_volume(bus))
+}
+
+pub fn set_volume(bus: SoundBus, volume: f32) {
+    with_director(|d| d.set_volume(bus, volume));
+}
+
+pub fn get_music_volume() -> f32 {
+    with_director(|d| d.get_music_volume())
+}
+
+pub fn set_music_volume(volume: f32) {
+    with_director(|d| d.set_music_volume(volume));
 }
 
 pub(crate) fn clean_se() {
     with_director(|d| d.clean_se());
 }
 
+pub(crate) fn update_music() {
+    with_director(|d| d.update_music());
+}
+
+pub(crate) fn update_voice() {
+    with_director(|d| d.update_voice());
+}
+
 pub fn get_variable<T, A>(index: A) -> Option<T>
     where T: DeserializeOwned, A: Into<String>
 {
@@ -1,11 +1,16 @@
 use std::thread::{ sleep };
-use std::time::{ Duration };
+use std::time::{ Duration, Instant };
 use time::{ Tm };
+use ::application::{ StepMode };
+use ::util::{ director };
 
 pub struct FpsManager {
     fps: u32,
     dt: i64,
     max_retry: i64,
+    prev_frame: Option<Instant>,
+    fixed_step: Option<i64>,
+    accumulator: i64,
     render_times: Vec<Tm>
 }
 
@@ -15,6 +20,9 @@ impl FpsManager {
         let fps_param = Self::generate_fps_param(fps);
         Self {
             render_times: Vec::new(),
+            prev_frame: None,
+            fixed_step: None,
+            accumulator: 0,
             dt: fps_param.0,
             max_retry: fps_param.1,
             fps: fps
@@ -28,15 +36,29 @@ impl FpsManager {
         self.fps = fps;
     }
 
+    pub fn set_step_mode(&mut self, step_mode: StepMode) {
+        self.fixed_step = match step_mode {
+            StepMode::Variable => None,
+            StepMode::Fixed(rate) => Some(Self::generate_fps_param(rate).0)
+        };
+        self.accumulator = 0;
+    }
+
     pub fn run<P, U, R>(&mut self, prev_sleep_time: i64, prepare: P, update: U, render: R) -> i64
-    where P: FnOnce() -> (), U: Fn() -> (), R: FnOnce() -> () {
+    where P: FnOnce() -> (), U: Fn() -> (), R: FnOnce(f32) -> () {
+        if let Some(step) = self.fixed_step {
+            return self.run_fixed(step, prepare, update, render);
+        }
         let (total_time, is_sleep) = Self::measure(|| {
             let prev_over_time = if prev_sleep_time > 0 { prev_sleep_time } else { 0 };
             let (render_time, _) = Self::measure(|| {
-                render();
+                render(0.0);
                 self.rendered();
             });
-            let (prepare_time, _) = Self::measure(|| prepare());
+            let (prepare_time, _) = Self::measure(|| {
+                self.tick_clock();
+                prepare();
+            });
             let mut update_time: i64 = 0;
             for i in 0..self.max_retry {
                 let (utime, _) = Self::measure(|| update());
@@ -54,6 +76,35 @@ impl FpsManager {
         if is_sleep { total_time - self.dt } else { 0 }
     }
 
+    fn run_fixed<P, U, R>(&mut self, step: i64, prepare: P, update: U, render: R) -> i64
+    where P: FnOnce() -> (), U: Fn() -> (), R: FnOnce(f32) -> () {
+        let (total_time, _) = Self::measure(|| {
+            prepare();
+            self.accumulate();
+            let mut count: i64 = 0;
+            while self.accumulator >= step && count < self.max_retry {
+                director::tick_clock(step / 1_000);
+                update();
+                self.accumulator -= step;
+                count += 1;
+            }
+            if self.accumulator >= step { self.accumulator %= step; }
+            render(self.accumulator as f32 / step as f32);
+            self.rendered();
+        });
+        let delay = self.dt - total_time;
+        if delay > 0 { sleep(Duration::new(0, delay as u32)); }
+        0
+    }
+
+    fn accumulate(&mut self) {
+        let now = Instant::now();
+        if let Some(prev_frame) = self.prev_frame {
+            self.accumulator += now.duration_since(prev_frame).as_nanos() as i64;
+        }
+        self.prev_frame = Some(now);
+    }
+
     fn generate_fps_param(fps: u32) -> (i64, i64) {
         (
             1_000_000_000 / (fps as i64),
@@ -71,6 +122,14 @@ impl FpsManager {
         )
     }
 
+    fn tick_clock(&mut self) {
+        let now = Instant::now();
+        if let Some(prev_frame) = self.prev_frame {
+            director::tick_clock(now.duration_since(prev_frame).as_micros() as i64);
+        }
+        self.prev_frame = Some(now);
+    }
+
     fn rendered(&mut self) {
         let now = time::now();
         while self.render_times.len() > 0 && (now - *self.render_times.first().unwrap()).num_seconds() > 9 {
@@ -0,0 +1,79 @@
+use std::rc::Rc;
+use std::path::{ Path, PathBuf };
+use ::node::scene::{ SceneLike };
+use ::util::{ HeadlessRunner, director, render_frame };
+use ::util::parameter::{ GoldenImageOption };
+use image::{ RgbaImage, Rgba, Pixel };
+
+pub fn render_scene_image(runner: &mut HeadlessRunner, scene: Rc<dyn SceneLike>, frames: u64) -> RgbaImage {
+    director::replace_scene(scene, ::NoOption);
+    runner.step_frames(frames);
+    render_frame(0.0);
+    director::capture_screen().unwrap()
+}
+
+pub fn check_golden_image<A, B>(runner: &mut HeadlessRunner, scene: Rc<dyn SceneLike>, path: A, option: B) -> Result<(), String>
+where A: AsRef<Path>, B: Into<GoldenImageOption>
+{
+    let o = option.into();
+    let actual = render_scene_image(runner, scene, o.frames);
+    compare_golden_image(&actual, path, &o)
+}
+
+pub fn compare_golden_image<A>(actual: &RgbaImage, path: A, option: &GoldenImageOption) -> Result<(), String>
+where A: AsRef<Path>
+{
+    let p = path.as_ref();
+    if option.update {
+        actual.save(p).map_err(|e| e.to_string())?;
+        return Ok(());
+    }
+    if !p.exists() {
+        let actual_path = sibling_path(p, "actual");
+        actual.save(&actual_path).map_err(|e| e.to_string())?;
+        return Err(format!("golden image missing: {} (actual: {})", p.display(), actual_path.display()));
+    }
+    let expected = image::open(p).map_err(|e| e.to_string())?.to_rgba();
+    if expected.dimensions() != actual.dimensions() {
+        actual.save(sibling_path(p, "actual")).map_err(|e| e.to_string())?;
+        return Err(format!(
+            "golden image size mismatch: {} expected {:?}, actual {:?}",
+            p.display(), expected.dimensions(), actual.dimensions()
+        ));
+    }
+    let (diff, mismatch) = generate_diff_image(&expected, actual, option.tolerance);
+    if mismatch <= option.max_mismatch_pixels { return Ok(()); }
+    let diff_path = sibling_path(p, "diff");
+    actual.save(sibling_path(p, "actual")).map_err(|e| e.to_string())?;
+    diff.save(&diff_path).map_err(|e| e.to_string())?;
+    Err(format!(
+        "golden image mismatch: {} ({} pixels differ, diff: {})",
+        p.display(), mismatch, diff_path.display()
+    ))
+}
+
+fn generate_diff_image(expected: &RgbaImage, actual: &RgbaImage, tolerance: u8) -> (RgbaImage, usize) {
+    let (width, height) = actual.dimensions();
+    let mut diff = RgbaImage::new(width, height);
+    let mut mismatch: usize = 0;
+    for y in 0..height {
+        for x in 0..width {
+            let (e, a) = (expected.get_pixel(x, y).channels(), actual.get_pixel(x, y).channels());
+            let over = e.iter().zip(a.iter()).any(|(ec, ac)| (*ec as i16 - *ac as i16).abs() > tolerance as i16);
+            let pixel = if over {
+                mismatch += 1;
+                Rgba::from_channels(255, 0, 0, 255)
+            } else {
+                let luma = ((a[0] as u32 + a[1] as u32 + a[2] as u32) / 3 / 4) as u8;
+                Rgba::from_channels(luma, luma, luma, 255)
+            };
+            diff.put_pixel(x, y, pixel);
+        }
+    }
+    (diff, mismatch)
+}
+
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("golden");
+    path.with_file_name(format!("{}.{}.png", stem, suffix))
+}
@@ -0,0 +1,71 @@
+use std::rc::Rc;
+use std::sync::atomic::{ AtomicBool, Ordering };
+use ::application::{ Application, StepMode };
+use ::util::{ director, with_context, initialize_application, release_application, prepare_frame, update_frame, render_frame, finish_frame };
+
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+pub struct HeadlessRunner {
+    application: Rc<dyn Application>,
+    frame: u64
+}
+
+impl HeadlessRunner {
+
+    pub fn new(application: Rc<dyn Application>) -> Result<Self, String> {
+        if RUNNING.compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst).is_err() {
+            return Err("HeadlessRunner is already running; drop it before creating another one".to_owned());
+        }
+        if let Err(e) = initialize_application(application.clone(), true) {
+            release_application();
+            RUNNING.store(false, Ordering::SeqCst);
+            return Err(e);
+        }
+        Ok(Self {
+            application: application,
+            frame: 0
+        })
+    }
+
+    pub fn step(&mut self) -> bool {
+        if !director::is_continuing() { return false; }
+        let rate = match self.application.step_mode() {
+            StepMode::Variable => self.application.fps(),
+            StepMode::Fixed(rate) => rate
+        };
+        director::tick_clock(1_000_000 / rate as i64);
+        prepare_frame();
+        update_frame();
+        render_frame(0.0);
+        finish_frame(self.application.clone());
+        self.frame += 1;
+        director::is_continuing()
+    }
+
+    pub fn step_frames(&mut self, frames: u64) -> bool {
+        for _ in 0..frames {
+            if !self.step() { return false; }
+        }
+        true
+    }
+
+    pub fn run(&mut self) {
+        while self.step() { }
+    }
+
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+}
+
+impl Drop for HeadlessRunner {
+
+    fn drop(&mut self) {
+        director::flush_config();
+        with_context(|c| c.close_audio());
+        release_application();
+        RUNNING.store(false, Ordering::SeqCst);
+    }
+
+}
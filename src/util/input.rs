@@ -1,5 +1,5 @@
 use ::util::{ director };
-use ::util::parameter::{ Point, InputInfo };
+use ::util::parameter::{ Point, InputInfo, TouchPoint, TouchGesture };
 
 pub fn is_press_start<A>(key: A) -> bool where A: Into<String> {
     get_input(key).is_press_start()
@@ -13,6 +13,50 @@ pub fn is_pressed<A>(key: A) -> bool where A: Into<String> {
     get_input(key).is_pressed()
 }
 
+pub fn is_repeat<A>(key: A) -> bool where A: Into<String> {
+    get_input(key).is_repeat()
+}
+
+pub fn set_key_repeat(delay: f64, interval: f64) {
+    director::set_key_repeat(delay, interval);
+}
+
+pub fn is_buffered<A>(key: A, steps: u32) -> bool where A: Into<String> {
+    director::is_input_buffered(None, key, steps)
+}
+
+pub fn consume_buffered<A>(key: A) where A: Into<String> {
+    director::consume_input_buffer(key);
+}
+
+pub fn push_context<A>(name: A, pass_through: bool) where A: Into<String> {
+    director::push_input_context(name, pass_through);
+}
+
+pub fn pop_context<A>(name: A) where A: Into<String> {
+    director::pop_input_context(name);
+}
+
+pub fn is_press_start_in<A>(context: &str, key: A) -> bool where A: Into<String> {
+    get_input_in(context, key).is_press_start()
+}
+
+pub fn is_pressing_in<A>(context: &str, key: A) -> bool where A: Into<String> {
+    get_input_in(context, key).is_pressing()
+}
+
+pub fn is_pressed_in<A>(context: &str, key: A) -> bool where A: Into<String> {
+    get_input_in(context, key).is_pressed()
+}
+
+pub fn is_repeat_in<A>(context: &str, key: A) -> bool where A: Into<String> {
+    get_input_in(context, key).is_repeat()
+}
+
+pub fn is_buffered_in<A>(context: &str, key: A, steps: u32) -> bool where A: Into<String> {
+    director::is_input_buffered(Some(context), key, steps)
+}
+
 pub fn is_click<A>(key: A) -> Option<Point> where A: Into<String> {
     let info = get_input(key);
     if !info.is_pressed() { return None; }
@@ -23,7 +67,26 @@ pub fn get_mouse_position() -> Point {
     director::get_mouse_position()
 }
 
+pub fn get_mouse_motion() -> Point {
+    director::get_mouse_motion()
+}
+
+pub fn get_mouse_wheel_delta() -> Point {
+    director::get_mouse_wheel_delta()
+}
+
+pub fn get_touches() -> Vec<TouchPoint> {
+    director::get_touches()
+}
+
+pub fn get_touch_gestures() -> Vec<TouchGesture> {
+    director::get_touch_gestures()
+}
+
 pub fn get_input<A>(key: A) -> InputInfo where A: Into<String> {
     director::get_input_info(key)
 }
 
+pub fn get_input_in<A>(context: &str, key: A) -> InputInfo where A: Into<String> {
+    director::get_input_info_in(Some(context), key)
+}
\ No newline at end of file
@@ -2,6 +2,9 @@ mod tool;
 mod fps_manager;
 mod validation;
 mod input;
+mod headless_runner;
+mod golden_image;
+mod rand_stream;
 pub mod director;
 pub mod parameter;
 pub mod easing;
@@ -11,4 +14,7 @@ pub use self::tool::*;
 pub use self::fps_manager::*;
 pub use self::validation::*;
 pub use self::input::*;
+pub use self::headless_runner::*;
+pub use self::golden_image::*;
+pub use self::rand_stream::*;
 pub use self::save_migrator::*;
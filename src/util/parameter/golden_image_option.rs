@@ -0,0 +1,61 @@
+use ::util::{ NoOption };
+
+#[derive(Clone)]
+pub struct GoldenImageOption {
+    pub frames: u64,
+    pub tolerance: u8,
+    pub max_mismatch_pixels: usize,
+    pub update: bool
+}
+
+impl Default for GoldenImageOption {
+
+    fn default() -> Self {
+        Self {
+            frames: 1,
+            tolerance: 0,
+            max_mismatch_pixels: 0,
+            update: std::env::var("RGENGINE_UPDATE_GOLDEN_IMAGE").is_ok()
+        }
+    }
+
+}
+
+impl From<&GoldenImageOption> for GoldenImageOption {
+
+    fn from(f: &GoldenImageOption) -> Self {
+        f.clone()
+    }
+
+}
+
+impl From<u64> for GoldenImageOption {
+
+    fn from(f: u64) -> Self {
+        Self {
+            frames: f,
+            ..Self::default()
+        }
+    }
+
+}
+
+impl From<(u64, u8)> for GoldenImageOption {
+
+    fn from(f: (u64, u8)) -> Self {
+        Self {
+            frames: f.0,
+            tolerance: f.1,
+            ..Self::default()
+        }
+    }
+
+}
+
+impl From<NoOption> for GoldenImageOption {
+
+    fn from(_: NoOption) -> Self {
+        Self::default()
+    }
+
+}
@@ -1,8 +1,9 @@
-use std::convert::TryInto;
+use std::convert::{ TryInto, TryFrom };
 use sdl2::keyboard::Keycode;
 use sdl2::mouse::MouseButton;
+use ::util::parameter::{ StickDirection };
 
-#[derive(Clone, Copy, Hash, Eq, PartialEq)]
+#[derive(Clone, Copy, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub enum InputCode {
     Backspace,
     Tab,
@@ -247,12 +248,38 @@ pub enum InputCode {
     MouseX2,
     MouseWheelUp,
     MouseWheelDown,
+    MouseWheelLeft,
+    MouseWheelRight,
     JoystickButton { index: i32, button: u8 },
     JoystickHatUp { index: i32 },
     JoystickHatDown { index: i32 },
     JoystickHatLeft { index: i32 },
     JoystickHatRight { index: i32 },
     JoystickAxis { index: i32 },
+    JoystickStick { index: i32, stick: u8 },
+    JoystickStickDirection { index: i32, stick: u8, direction: StickDirection },
+    JoystickTrigger { index: i32, axis: u8 },
+    ControllerA { player: u32 },
+    ControllerB { player: u32 },
+    ControllerX { player: u32 },
+    ControllerY { player: u32 },
+    ControllerBack { player: u32 },
+    ControllerGuide { player: u32 },
+    ControllerStart { player: u32 },
+    ControllerLeftStickButton { player: u32 },
+    ControllerRightStickButton { player: u32 },
+    ControllerLeftShoulder { player: u32 },
+    ControllerRightShoulder { player: u32 },
+    ControllerLeftTrigger { player: u32 },
+    ControllerRightTrigger { player: u32 },
+    ControllerDPadUp { player: u32 },
+    ControllerDPadDown { player: u32 },
+    ControllerDPadLeft { player: u32 },
+    ControllerDPadRight { player: u32 },
+    ControllerLeftStick { player: u32 },
+    ControllerRightStick { player: u32 },
+    ControllerLeftStickDirection { player: u32, direction: StickDirection },
+    ControllerRightStickDirection { player: u32, direction: StickDirection },
 }
 
 impl TryInto<Keycode> for InputCode {
@@ -518,4 +545,268 @@ impl TryInto<MouseButton> for InputCode {
         }
     }
 
-}
\ No newline at end of file
+}
+
+impl TryFrom<Keycode> for InputCode {
+
+    type Error = String;
+
+    fn try_from(f: Keycode) -> Result<InputCode, String> {
+        match f {
+            Keycode::Backspace => { Ok(InputCode::Backspace) },
+            Keycode::Tab => { Ok(InputCode::Tab) },
+            Keycode::Return => { Ok(InputCode::Return) },
+            Keycode::Escape => { Ok(InputCode::Escape) },
+            Keycode::Space => { Ok(InputCode::Space) },
+            Keycode::Exclaim => { Ok(InputCode::Exclaim) },
+            Keycode::Quotedbl => { Ok(InputCode::Quotedbl) },
+            Keycode::Hash => { Ok(InputCode::Hash) },
+            Keycode::Dollar => { Ok(InputCode::Dollar) },
+            Keycode::Percent => { Ok(InputCode::Percent) },
+            Keycode::Ampersand => { Ok(InputCode::Ampersand) },
+            Keycode::Quote => { Ok(InputCode::Quote) },
+            Keycode::LeftParen => { Ok(InputCode::LeftParen) },
+            Keycode::RightParen => { Ok(InputCode::RightParen) },
+            Keycode::Asterisk => { Ok(InputCode::Asterisk) },
+            Keycode::Plus => { Ok(InputCode::Plus) },
+            Keycode::Comma => { Ok(InputCode::Comma) },
+            Keycode::Minus => { Ok(InputCode::Minus) },
+            Keycode::Period => { Ok(InputCode::Period) },
+            Keycode::Slash => { Ok(InputCode::Slash) },
+            Keycode::Num0 => { Ok(InputCode::Num0) },
+            Keycode::Num1 => { Ok(InputCode::Num1) },
+            Keycode::Num2 => { Ok(InputCode::Num2) },
+            Keycode::Num3 => { Ok(InputCode::Num3) },
+            Keycode::Num4 => { Ok(InputCode::Num4) },
+            Keycode::Num5 => { Ok(InputCode::Num5) },
+            Keycode::Num6 => { Ok(InputCode::Num6) },
+            Keycode::Num7 => { Ok(InputCode::Num7) },
+            Keycode::Num8 => { Ok(InputCode::Num8) },
+            Keycode::Num9 => { Ok(InputCode::Num9) },
+            Keycode::Colon => { Ok(InputCode::Colon) },
+            Keycode::Semicolon => { Ok(InputCode::Semicolon) },
+            Keycode::Less => { Ok(InputCode::Less) },
+            Keycode::Equals => { Ok(InputCode::Equals) },
+            Keycode::Greater => { Ok(InputCode::Greater) },
+            Keycode::Question => { Ok(InputCode::Question) },
+            Keycode::At => { Ok(InputCode::At) },
+            Keycode::LeftBracket => { Ok(InputCode::LeftBracket) },
+            Keycode::Backslash => { Ok(InputCode::Backslash) },
+            Keycode::RightBracket => { Ok(InputCode::RightBracket) },
+            Keycode::Caret => { Ok(InputCode::Caret) },
+            Keycode::Underscore => { Ok(InputCode::Underscore) },
+            Keycode::Backquote => { Ok(InputCode::Backquote) },
+            Keycode::A => { Ok(InputCode::A) },
+            Keycode::B => { Ok(InputCode::B) },
+            Keycode::C => { Ok(InputCode::C) },
+            Keycode::D => { Ok(InputCode::D) },
+            Keycode::E => { Ok(InputCode::E) },
+            Keycode::F => { Ok(InputCode::F) },
+            Keycode::G => { Ok(InputCode::G) },
+            Keycode::H => { Ok(InputCode::H) },
+            Keycode::I => { Ok(InputCode::I) },
+            Keycode::J => { Ok(InputCode::J) },
+            Keycode::K => { Ok(InputCode::K) },
+            Keycode::L => { Ok(InputCode::L) },
+            Keycode::M => { Ok(InputCode::M) },
+            Keycode::N => { Ok(InputCode::N) },
+            Keycode::O => { Ok(InputCode::O) },
+            Keycode::P => { Ok(InputCode::P) },
+            Keycode::Q => { Ok(InputCode::Q) },
+            Keycode::R => { Ok(InputCode::R) },
+            Keycode::S => { Ok(InputCode::S) },
+            Keycode::T => { Ok(InputCode::T) },
+            Keycode::U => { Ok(InputCode::U) },
+            Keycode::V => { Ok(InputCode::V) },
+            Keycode::W => { Ok(InputCode::W) },
+            Keycode::X => { Ok(InputCode::X) },
+            Keycode::Y => { Ok(InputCode::Y) },
+            Keycode::Z => { Ok(InputCode::Z) },
+            Keycode::Delete => { Ok(InputCode::Delete) },
+            Keycode::CapsLock => { Ok(InputCode::CapsLock) },
+            Keycode::F1 => { Ok(InputCode::F1) },
+            Keycode::F2 => { Ok(InputCode::F2) },
+            Keycode::F3 => { Ok(InputCode::F3) },
+            Keycode::F4 => { Ok(InputCode::F4) },
+            Keycode::F5 => { Ok(InputCode::F5) },
+            Keycode::F6 => { Ok(InputCode::F6) },
+            Keycode::F7 => { Ok(InputCode::F7) },
+            Keycode::F8 => { Ok(InputCode::F8) },
+            Keycode::F9 => { Ok(InputCode::F9) },
+            Keycode::F10 => { Ok(InputCode::F10) },
+            Keycode::F11 => { Ok(InputCode::F11) },
+            Keycode::F12 => { Ok(InputCode::F12) },
+            Keycode::PrintScreen => { Ok(InputCode::PrintScreen) },
+            Keycode::ScrollLock => { Ok(InputCode::ScrollLock) },
+            Keycode::Pause => { Ok(InputCode::Pause) },
+            Keycode::Insert => { Ok(InputCode::Insert) },
+            Keycode::Home => { Ok(InputCode::Home) },
+            Keycode::PageUp => { Ok(InputCode::PageUp) },
+            Keycode::End => { Ok(InputCode::End) },
+            Keycode::PageDown => { Ok(InputCode::PageDown) },
+            Keycode::Right => { Ok(InputCode::Right) },
+            Keycode::Left => { Ok(InputCode::Left) },
+            Keycode::Down => { Ok(InputCode::Down) },
+            Keycode::Up => { Ok(InputCode::Up) },
+            Keycode::NumLockClear => { Ok(InputCode::NumLockClear) },
+            Keycode::KpDivide => { Ok(InputCode::KpDivide) },
+            Keycode::KpMultiply => { Ok(InputCode::KpMultiply) },
+            Keycode::KpMinus => { Ok(InputCode::KpMinus) },
+            Keycode::KpPlus => { Ok(InputCode::KpPlus) },
+            Keycode::KpEnter => { Ok(InputCode::KpEnter) },
+            Keycode::Kp1 => { Ok(InputCode::Kp1) },
+            Keycode::Kp2 => { Ok(InputCode::Kp2) },
+            Keycode::Kp3 => { Ok(InputCode::Kp3) },
+            Keycode::Kp4 => { Ok(InputCode::Kp4) },
+            Keycode::Kp5 => { Ok(InputCode::Kp5) },
+            Keycode::Kp6 => { Ok(InputCode::Kp6) },
+            Keycode::Kp7 => { Ok(InputCode::Kp7) },
+            Keycode::Kp8 => { Ok(InputCode::Kp8) },
+            Keycode::Kp9 => { Ok(InputCode::Kp9) },
+            Keycode::Kp0 => { Ok(InputCode::Kp0) },
+            Keycode::KpPeriod => { Ok(InputCode::KpPeriod) },
+            Keycode::Application => { Ok(InputCode::Application) },
+            Keycode::Power => { Ok(InputCode::Power) },
+            Keycode::KpEquals => { Ok(InputCode::KpEquals) },
+            Keycode::F13 => { Ok(InputCode::F13) },
+            Keycode::F14 => { Ok(InputCode::F14) },
+            Keycode::F15 => { Ok(InputCode::F15) },
+            Keycode::F16 => { Ok(InputCode::F16) },
+            Keycode::F17 => { Ok(InputCode::F17) },
+            Keycode::F18 => { Ok(InputCode::F18) },
+            Keycode::F19 => { Ok(InputCode::F19) },
+            Keycode::F20 => { Ok(InputCode::F20) },
+            Keycode::F21 => { Ok(InputCode::F21) },
+            Keycode::F22 => { Ok(InputCode::F22) },
+            Keycode::F23 => { Ok(InputCode::F23) },
+            Keycode::F24 => { Ok(InputCode::F24) },
+            Keycode::Execute => { Ok(InputCode::Execute) },
+            Keycode::Help => { Ok(InputCode::Help) },
+            Keycode::Menu => { Ok(InputCode::Menu) },
+            Keycode::Select => { Ok(InputCode::Select) },
+            Keycode::Stop => { Ok(InputCode::Stop) },
+            Keycode::Again => { Ok(InputCode::Again) },
+            Keycode::Undo => { Ok(InputCode::Undo) },
+            Keycode::Cut => { Ok(InputCode::Cut) },
+            Keycode::Copy => { Ok(InputCode::Copy) },
+            Keycode::Paste => { Ok(InputCode::Paste) },
+            Keycode::Find => { Ok(InputCode::Find) },
+            Keycode::Mute => { Ok(InputCode::Mute) },
+            Keycode::VolumeUp => { Ok(InputCode::VolumeUp) },
+            Keycode::VolumeDown => { Ok(InputCode::VolumeDown) },
+            Keycode::KpComma => { Ok(InputCode::KpComma) },
+            Keycode::KpEqualsAS400 => { Ok(InputCode::KpEqualsAS400) },
+            Keycode::AltErase => { Ok(InputCode::AltErase) },
+            Keycode::Sysreq => { Ok(InputCode::Sysreq) },
+            Keycode::Cancel => { Ok(InputCode::Cancel) },
+            Keycode::Clear => { Ok(InputCode::Clear) },
+            Keycode::Prior => { Ok(InputCode::Prior) },
+            Keycode::Return2 => { Ok(InputCode::Return2) },
+            Keycode::Separator => { Ok(InputCode::Separator) },
+            Keycode::Out => { Ok(InputCode::Out) },
+            Keycode::Oper => { Ok(InputCode::Oper) },
+            Keycode::ClearAgain => { Ok(InputCode::ClearAgain) },
+            Keycode::CrSel => { Ok(InputCode::CrSel) },
+            Keycode::ExSel => { Ok(InputCode::ExSel) },
+            Keycode::Kp00 => { Ok(InputCode::Kp00) },
+            Keycode::Kp000 => { Ok(InputCode::Kp000) },
+            Keycode::ThousandsSeparator => { Ok(InputCode::ThousandsSeparator) },
+            Keycode::DecimalSeparator => { Ok(InputCode::DecimalSeparator) },
+            Keycode::CurrencyUnit => { Ok(InputCode::CurrencyUnit) },
+            Keycode::CurrencySubUnit => { Ok(InputCode::CurrencySubUnit) },
+            Keycode::KpLeftParen => { Ok(InputCode::KpLeftParen) },
+            Keycode::KpRightParen => { Ok(InputCode::KpRightParen) },
+            Keycode::KpLeftBrace => { Ok(InputCode::KpLeftBrace) },
+            Keycode::KpRightBrace => { Ok(InputCode::KpRightBrace) },
+            Keycode::KpTab => { Ok(InputCode::KpTab) },
+            Keycode::KpBackspace => { Ok(InputCode::KpBackspace) },
+            Keycode::KpA => { Ok(InputCode::KpA) },
+            Keycode::KpB => { Ok(InputCode::KpB) },
+            Keycode::KpC => { Ok(InputCode::KpC) },
+            Keycode::KpD => { Ok(InputCode::KpD) },
+            Keycode::KpE => { Ok(InputCode::KpE) },
+            Keycode::KpF => { Ok(InputCode::KpF) },
+            Keycode::KpXor => { Ok(InputCode::KpXor) },
+            Keycode::KpPower => { Ok(InputCode::KpPower) },
+            Keycode::KpPercent => { Ok(InputCode::KpPercent) },
+            Keycode::KpLess => { Ok(InputCode::KpLess) },
+            Keycode::KpGreater => { Ok(InputCode::KpGreater) },
+            Keycode::KpAmpersand => { Ok(InputCode::KpAmpersand) },
+            Keycode::KpDblAmpersand => { Ok(InputCode::KpDblAmpersand) },
+            Keycode::KpVerticalBar => { Ok(InputCode::KpVerticalBar) },
+            Keycode::KpDblVerticalBar => { Ok(InputCode::KpDblVerticalBar) },
+            Keycode::KpColon => { Ok(InputCode::KpColon) },
+            Keycode::KpHash => { Ok(InputCode::KpHash) },
+            Keycode::KpSpace => { Ok(InputCode::KpSpace) },
+            Keycode::KpAt => { Ok(InputCode::KpAt) },
+            Keycode::KpExclam => { Ok(InputCode::KpExclam) },
+            Keycode::KpMemStore => { Ok(InputCode::KpMemStore) },
+            Keycode::KpMemRecall => { Ok(InputCode::KpMemRecall) },
+            Keycode::KpMemClear => { Ok(InputCode::KpMemClear) },
+            Keycode::KpMemAdd => { Ok(InputCode::KpMemAdd) },
+            Keycode::KpMemSubtract => { Ok(InputCode::KpMemSubtract) },
+            Keycode::KpMemMultiply => { Ok(InputCode::KpMemMultiply) },
+            Keycode::KpMemDivide => { Ok(InputCode::KpMemDivide) },
+            Keycode::KpPlusMinus => { Ok(InputCode::KpPlusMinus) },
+            Keycode::KpClear => { Ok(InputCode::KpClear) },
+            Keycode::KpClearEntry => { Ok(InputCode::KpClearEntry) },
+            Keycode::KpBinary => { Ok(InputCode::KpBinary) },
+            Keycode::KpOctal => { Ok(InputCode::KpOctal) },
+            Keycode::KpDecimal => { Ok(InputCode::KpDecimal) },
+            Keycode::KpHexadecimal => { Ok(InputCode::KpHexadecimal) },
+            Keycode::LCtrl => { Ok(InputCode::LCtrl) },
+            Keycode::LShift => { Ok(InputCode::LShift) },
+            Keycode::LAlt => { Ok(InputCode::LAlt) },
+            Keycode::LGui => { Ok(InputCode::LGui) },
+            Keycode::RCtrl => { Ok(InputCode::RCtrl) },
+            Keycode::RShift => { Ok(InputCode::RShift) },
+            Keycode::RAlt => { Ok(InputCode::RAlt) },
+            Keycode::RGui => { Ok(InputCode::RGui) },
+            Keycode::Mode => { Ok(InputCode::Mode) },
+            Keycode::AudioNext => { Ok(InputCode::AudioNext) },
+            Keycode::AudioPrev => { Ok(InputCode::AudioPrev) },
+            Keycode::AudioStop => { Ok(InputCode::AudioStop) },
+            Keycode::AudioPlay => { Ok(InputCode::AudioPlay) },
+            Keycode::AudioMute => { Ok(InputCode::AudioMute) },
+            Keycode::MediaSelect => { Ok(InputCode::MediaSelect) },
+            Keycode::Www => { Ok(InputCode::Www) },
+            Keycode::Mail => { Ok(InputCode::Mail) },
+            Keycode::Calculator => { Ok(InputCode::Calculator) },
+            Keycode::Computer => { Ok(InputCode::Computer) },
+            Keycode::AcSearch => { Ok(InputCode::AcSearch) },
+            Keycode::AcHome => { Ok(InputCode::AcHome) },
+            Keycode::AcBack => { Ok(InputCode::AcBack) },
+            Keycode::AcForward => { Ok(InputCode::AcForward) },
+            Keycode::AcStop => { Ok(InputCode::AcStop) },
+            Keycode::AcRefresh => { Ok(InputCode::AcRefresh) },
+            Keycode::AcBookmarks => { Ok(InputCode::AcBookmarks) },
+            Keycode::BrightnessDown => { Ok(InputCode::BrightnessDown) },
+            Keycode::BrightnessUp => { Ok(InputCode::BrightnessUp) },
+            Keycode::DisplaySwitch => { Ok(InputCode::DisplaySwitch) },
+            Keycode::KbdIllumToggle => { Ok(InputCode::KbdIllumToggle) },
+            Keycode::KbdIllumDown => { Ok(InputCode::KbdIllumDown) },
+            Keycode::KbdIllumUp => { Ok(InputCode::KbdIllumUp) },
+            Keycode::Eject => { Ok(InputCode::Eject) },
+            Keycode::Sleep => { Ok(InputCode::Sleep) },
+            _ => { Err("unmatched".to_owned()) }
+        }
+    }
+
+}
+
+impl TryFrom<MouseButton> for InputCode {
+
+    type Error = String;
+
+    fn try_from(f: MouseButton) -> Result<InputCode, String> {
+        match f {
+            MouseButton::Unknown => { Ok(InputCode::MouseUnknown) },
+            MouseButton::Left => { Ok(InputCode::MouseLeft) },
+            MouseButton::Middle => { Ok(InputCode::MouseMiddle) },
+            MouseButton::Right => { Ok(InputCode::MouseRight) },
+            MouseButton::X1 => { Ok(InputCode::MouseX1) },
+            MouseButton::X2 => { Ok(InputCode::MouseX2) },
+        }
+    }
+
+}
@@ -1,6 +1,7 @@
 use ::util::parameter::{ InputCode };
 use std::collections::HashMap;
 
+#[derive(Clone, Serialize, Deserialize)]
 pub struct InputCodeMap {
     map: HashMap<String, Vec<InputCode>>
 }
@@ -19,6 +20,27 @@ impl InputCodeMap {
         self.map.get(&key.into()).cloned().unwrap_or(Vec::new())
     }
 
+    pub fn keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self.map.keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    pub fn get<A>(&self, key: A) -> Vec<InputCode>
+        where A: Into<String>
+    {
+        self.convert_key(key)
+    }
+
+    pub fn find_keys(&self, code: &InputCode) -> Vec<String> {
+        let mut keys: Vec<String> = Vec::new();
+        for (key, codes) in &self.map {
+            if codes.contains(code) { keys.push(key.to_string()); }
+        }
+        keys.sort();
+        keys
+    }
+
     pub fn insert<A>(&mut self, key: A, code: InputCode)
         where A: Into<String>
     {
@@ -26,7 +48,22 @@ impl InputCodeMap {
         if self.map.get(&k).is_none() {
             self.map.insert(k.clone(), Vec::new());
         }
-        self.map.get_mut(&k).unwrap().push(code);
+        let codes = self.map.get_mut(&k).unwrap();
+        if !codes.contains(&code) { codes.push(code); }
+    }
+
+    pub fn remove<A>(&mut self, key: A, code: &InputCode)
+        where A: Into<String>
+    {
+        if let Some(codes) = self.map.get_mut(&key.into()) {
+            codes.retain(|c| c != code);
+        }
+    }
+
+    pub fn replace<A>(&mut self, key: A, codes: Vec<InputCode>)
+        where A: Into<String>
+    {
+        self.map.insert(key.into(), codes);
     }
 
     pub fn reset<A>(&mut self, key: Option<A>)
@@ -44,3 +81,46 @@ impl InputCodeMap {
 
 }
 
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn insert_ignores_duplicate_codes() {
+        let mut map = InputCodeMap::new();
+        map.insert("confirm", InputCode::Return);
+        map.insert("confirm", InputCode::Return);
+        map.insert("confirm", InputCode::Space);
+        assert!(map.get("confirm") == vec![InputCode::Return, InputCode::Space]);
+        assert!(map.get("cancel").is_empty());
+    }
+
+    #[test]
+    fn find_keys_returns_sorted_keys() {
+        let mut map = InputCodeMap::new();
+        map.insert("menu", InputCode::Escape);
+        map.insert("cancel", InputCode::Escape);
+        map.insert("confirm", InputCode::Return);
+        assert_eq!(map.find_keys(&InputCode::Escape), vec!["cancel".to_owned(), "menu".to_owned()]);
+        assert_eq!(map.keys(), vec!["cancel".to_owned(), "confirm".to_owned(), "menu".to_owned()]);
+    }
+
+    #[test]
+    fn remove_replace_and_reset() {
+        let mut map = InputCodeMap::new();
+        map.insert("confirm", InputCode::Return);
+        map.insert("confirm", InputCode::Space);
+        map.remove("confirm", &InputCode::Return);
+        assert!(map.get("confirm") == vec![InputCode::Space]);
+        map.replace("confirm", vec![InputCode::Z]);
+        assert!(map.get("confirm") == vec![InputCode::Z]);
+        map.insert("cancel", InputCode::X);
+        map.reset(Some("confirm"));
+        assert!(map.get("confirm").is_empty());
+        assert_eq!(map.keys(), vec!["cancel".to_owned(), "confirm".to_owned()]);
+        map.reset::<String>(None);
+        assert!(map.keys().is_empty());
+    }
+
+}
@@ -0,0 +1,304 @@
+use std::convert::{ TryInto, TryFrom };
+use ::util::parameter::{ InputCode, PressState };
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use sdl2::mouse::{ MouseButton, MouseWheelDirection };
+use sdl2::joystick::HatState;
+use sdl2::controller::{ Button, Axis };
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum InputEvent {
+    Quit,
+    KeyDown { keycode: i32 },
+    KeyUp { keycode: i32 },
+    MouseMotion { x: i32, y: i32, #[serde(default)] xrel: i32, #[serde(default)] yrel: i32 },
+    MouseButtonDown { button: u8 },
+    MouseButtonUp { button: u8 },
+    MouseWheel { #[serde(default)] x: i32, y: i32 },
+    JoyHatMotion { which: i32, state: u8 },
+    JoyButtonDown { which: i32, button: u8 },
+    JoyButtonUp { which: i32, button: u8 },
+    JoyAxisMotion { which: i32, axis: u8, value: i16 },
+    JoyDeviceRemoved { which: i32 },
+    ControllerDeviceAdded { which: i32 },
+    ControllerDeviceRemoved { which: i32 },
+    ControllerButtonDown { which: i32, button: u8 },
+    ControllerButtonUp { which: i32, button: u8 },
+    ControllerAxisMotion { which: i32, axis: u8, value: i16 },
+    TextInput { text: String },
+    TextEditing { text: String, start: i32, length: i32 },
+    FingerDown { finger_id: i64, x: f32, y: f32 },
+    FingerMotion { finger_id: i64, x: f32, y: f32 },
+    FingerUp { finger_id: i64, x: f32, y: f32 }
+}
+
+impl InputEvent {
+
+    pub fn from_sdl_event(event: &Event) -> Option<Self> {
+        match event {
+            Event::Quit { .. } => {
+                Some(InputEvent::Quit)
+            },
+            Event::KeyDown { keycode: Some(k), .. } => {
+                Some(InputEvent::KeyDown { keycode: *k as i32 })
+            },
+            Event::KeyUp { keycode: Some(k), .. } => {
+                Some(InputEvent::KeyUp { keycode: *k as i32 })
+            },
+            Event::MouseMotion { x, y, xrel, yrel, .. } => {
+                Some(InputEvent::MouseMotion { x: *x, y: *y, xrel: *xrel, yrel: *yrel })
+            },
+            Event::MouseButtonDown { mouse_btn, .. } => {
+                Some(InputEvent::MouseButtonDown { button: Self::mouse_button_to_u8(mouse_btn) })
+            },
+            Event::MouseButtonUp { mouse_btn, .. } => {
+                Some(InputEvent::MouseButtonUp { button: Self::mouse_button_to_u8(mouse_btn) })
+            },
+            Event::MouseWheel { x, y, direction, .. } => {
+                let sign = if *direction == MouseWheelDirection::Flipped { -1 } else { 1 };
+                Some(InputEvent::MouseWheel { x: *x * sign, y: *y * sign })
+            },
+            Event::JoyHatMotion { which, state, .. } => {
+                Some(InputEvent::JoyHatMotion { which: *which, state: Self::hat_state_to_u8(state) })
+            },
+            Event::JoyButtonDown { which, button_idx, .. } => {
+                Some(InputEvent::JoyButtonDown { which: *which, button: *button_idx })
+            },
+            Event::JoyButtonUp { which, button_idx, .. } => {
+                Some(InputEvent::JoyButtonUp { which: *which, button: *button_idx })
+            },
+            Event::JoyAxisMotion { which, axis_idx, value, .. } => {
+                Some(InputEvent::JoyAxisMotion { which: *which, axis: *axis_idx, value: *value })
+            },
+            Event::JoyDeviceRemoved { which, .. } => {
+                Some(InputEvent::JoyDeviceRemoved { which: *which })
+            },
+            Event::ControllerDeviceRemoved { which, .. } => {
+                Some(InputEvent::ControllerDeviceRemoved { which: *which })
+            },
+            Event::ControllerButtonDown { which, button, .. } => {
+                Some(InputEvent::ControllerButtonDown { which: *which, button: Self::controller_button_code(button) })
+            },
+            Event::ControllerButtonUp { which, button, .. } => {
+                Some(InputEvent::ControllerButtonUp { which: *which, button: Self::controller_button_code(button) })
+            },
+            Event::ControllerAxisMotion { which, axis, value, .. } => {
+                Some(InputEvent::ControllerAxisMotion { which: *which, axis: Self::controller_axis_code(axis), value: *value })
+            },
+            Event::TextInput { text, .. } => {
+                Some(InputEvent::TextInput { text: text.clone() })
+            },
+            Event::TextEditing { text, start, length, .. } => {
+                Some(InputEvent::TextEditing { text: text.clone(), start: *start, length: *length })
+            },
+            Event::FingerDown { finger_id, x, y, .. } => {
+                Some(InputEvent::FingerDown { finger_id: *finger_id, x: *x, y: *y })
+            },
+            Event::FingerMotion { finger_id, x, y, .. } => {
+                Some(InputEvent::FingerMotion { finger_id: *finger_id, x: *x, y: *y })
+            },
+            Event::FingerUp { finger_id, x, y, .. } => {
+                Some(InputEvent::FingerUp { finger_id: *finger_id, x: *x, y: *y })
+            },
+            _ => { None }
+        }
+    }
+
+    pub fn from_input_code(code: InputCode, state: PressState) -> Option<Self> {
+        let pressed = state == PressState::Pressed;
+        let keycode: Result<Keycode, String> = code.clone().try_into();
+        if let Ok(k) = keycode {
+            return Some(if pressed { InputEvent::KeyDown { keycode: k as i32 } } else { InputEvent::KeyUp { keycode: k as i32 } });
+        }
+        let mouse: Result<MouseButton, String> = code.clone().try_into();
+        if let Ok(m) = mouse {
+            let button = Self::mouse_button_to_u8(&m);
+            return Some(if pressed { InputEvent::MouseButtonDown { button: button } } else { InputEvent::MouseButtonUp { button: button } });
+        }
+        if !pressed {
+            return match code {
+                InputCode::JoystickButton { index, button } => Some(InputEvent::JoyButtonUp { which: index, button: button }),
+                InputCode::JoystickHatUp { index } |
+                InputCode::JoystickHatDown { index } |
+                InputCode::JoystickHatLeft { index } |
+                InputCode::JoystickHatRight { index } => Some(InputEvent::JoyHatMotion { which: index, state: Self::hat_state_to_u8(&HatState::Centered) }),
+                _ => None
+            };
+        }
+        match code {
+            InputCode::MouseWheelUp => Some(InputEvent::MouseWheel { x: 0, y: 1 }),
+            InputCode::MouseWheelDown => Some(InputEvent::MouseWheel { x: 0, y: -1 }),
+            InputCode::MouseWheelLeft => Some(InputEvent::MouseWheel { x: -1, y: 0 }),
+            InputCode::MouseWheelRight => Some(InputEvent::MouseWheel { x: 1, y: 0 }),
+            InputCode::JoystickButton { index, button } => Some(InputEvent::JoyButtonDown { which: index, button: button }),
+            InputCode::JoystickHatUp { index } => Some(InputEvent::JoyHatMotion { which: index, state: Self::hat_state_to_u8(&HatState::Up) }),
+            InputCode::JoystickHatDown { index } => Some(InputEvent::JoyHatMotion { which: index, state: Self::hat_state_to_u8(&HatState::Down) }),
+            InputCode::JoystickHatLeft { index } => Some(InputEvent::JoyHatMotion { which: index, state: Self::hat_state_to_u8(&HatState::Left) }),
+            InputCode::JoystickHatRight { index } => Some(InputEvent::JoyHatMotion { which: index, state: Self::hat_state_to_u8(&HatState::Right) }),
+            _ => None
+        }
+    }
+
+    pub fn pressed_input_code(&self) -> Option<InputCode> {
+        match self {
+            InputEvent::KeyDown { keycode } => {
+                Self::keycode(*keycode).and_then(|k| InputCode::try_from(k).ok())
+            },
+            InputEvent::MouseButtonDown { button } => {
+                InputCode::try_from(Self::mouse_button(*button)).ok()
+            },
+            InputEvent::MouseWheel { x, y } => {
+                if *y > 0 { Some(InputCode::MouseWheelUp) }
+                else if *y < 0 { Some(InputCode::MouseWheelDown) }
+                else if *x < 0 { Some(InputCode::MouseWheelLeft) }
+                else if *x > 0 { Some(InputCode::MouseWheelRight) }
+                else { None }
+            },
+            InputEvent::JoyButtonDown { which, button } => {
+                Some(InputCode::JoystickButton { index: *which, button: *button })
+            },
+            InputEvent::JoyHatMotion { which, state } => {
+                match Self::hat_state(*state) {
+                    HatState::Up => Some(InputCode::JoystickHatUp { index: *which }),
+                    HatState::Down => Some(InputCode::JoystickHatDown { index: *which }),
+                    HatState::Left => Some(InputCode::JoystickHatLeft { index: *which }),
+                    HatState::Right => Some(InputCode::JoystickHatRight { index: *which }),
+                    _ => None
+                }
+            },
+            _ => { None }
+        }
+    }
+
+    pub(crate) fn keycode(keycode: i32) -> Option<Keycode> {
+        Keycode::from_i32(keycode)
+    }
+
+    pub(crate) fn mouse_button(button: u8) -> MouseButton {
+        match button {
+            1 => MouseButton::Left,
+            2 => MouseButton::Middle,
+            3 => MouseButton::Right,
+            4 => MouseButton::X1,
+            5 => MouseButton::X2,
+            _ => MouseButton::Unknown
+        }
+    }
+
+    pub(crate) fn hat_state(state: u8) -> HatState {
+        match state {
+            1 => HatState::Up,
+            2 => HatState::Right,
+            3 => HatState::RightUp,
+            4 => HatState::Down,
+            6 => HatState::RightDown,
+            8 => HatState::Left,
+            9 => HatState::LeftUp,
+            12 => HatState::LeftDown,
+            _ => HatState::Centered
+        }
+    }
+
+    pub(crate) fn controller_button(button: u8) -> Button {
+        match button {
+            0 => Button::A,
+            1 => Button::B,
+            2 => Button::X,
+            3 => Button::Y,
+            4 => Button::Back,
+            5 => Button::Guide,
+            6 => Button::Start,
+            7 => Button::LeftStick,
+            8 => Button::RightStick,
+            9 => Button::LeftShoulder,
+            10 => Button::RightShoulder,
+            11 => Button::DPadUp,
+            12 => Button::DPadDown,
+            13 => Button::DPadLeft,
+            _ => Button::DPadRight
+        }
+    }
+
+    pub(crate) fn controller_axis(axis: u8) -> Axis {
+        match axis {
+            0 => Axis::LeftX,
+            1 => Axis::LeftY,
+            2 => Axis::RightX,
+            3 => Axis::RightY,
+            4 => Axis::TriggerLeft,
+            _ => Axis::TriggerRight
+        }
+    }
+
+    pub(crate) fn controller_button_code(button: &Button) -> u8 {
+        match button {
+            Button::A => 0,
+            Button::B => 1,
+            Button::X => 2,
+            Button::Y => 3,
+            Button::Back => 4,
+            Button::Guide => 5,
+            Button::Start => 6,
+            Button::LeftStick => 7,
+            Button::RightStick => 8,
+            Button::LeftShoulder => 9,
+            Button::RightShoulder => 10,
+            Button::DPadUp => 11,
+            Button::DPadDown => 12,
+            Button::DPadLeft => 13,
+            Button::DPadRight => 14
+        }
+    }
+
+    pub(crate) fn controller_axis_code(axis: &Axis) -> u8 {
+        match axis {
+            Axis::LeftX => 0,
+            Axis::LeftY => 1,
+            Axis::RightX => 2,
+            Axis::RightY => 3,
+            Axis::TriggerLeft => 4,
+            Axis::TriggerRight => 5
+        }
+    }
+
+    fn mouse_button_to_u8(button: &MouseButton) -> u8 {
+        match button {
+            MouseButton::Left => 1,
+            MouseButton::Middle => 2,
+            MouseButton::Right => 3,
+            MouseButton::X1 => 4,
+            MouseButton::X2 => 5,
+            MouseButton::Unknown => 0
+        }
+    }
+
+    fn hat_state_to_u8(state: &HatState) -> u8 {
+        match state {
+            HatState::Centered => 0,
+            HatState::Up => 1,
+            HatState::Right => 2,
+            HatState::RightUp => 3,
+            HatState::Down => 4,
+            HatState::RightDown => 6,
+            HatState::Left => 8,
+            HatState::LeftUp => 9,
+            HatState::LeftDown => 12
+        }
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn hat_release_centers_the_hat() {
+        let pressed = InputEvent::from_input_code(InputCode::JoystickHatUp { index: 0 }, PressState::Pressed);
+        let released = InputEvent::from_input_code(InputCode::JoystickHatUp { index: 0 }, PressState::Released);
+        assert!(pressed == Some(InputEvent::JoyHatMotion { which: 0, state: 1 }));
+        assert!(released == Some(InputEvent::JoyHatMotion { which: 0, state: 0 }));
+    }
+
+}
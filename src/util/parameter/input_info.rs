@@ -4,8 +4,13 @@ pub struct InputInfo {
     pub(crate) press_start: bool,
     pub(crate) pressing: bool,
     pub(crate) pressed: bool,
+    pub(crate) repeat: bool,
     pub(crate) mouse_position: Point,
-    pub(crate) axis_position: Point
+    pub(crate) mouse_motion: Point,
+    pub(crate) wheel_delta: Point,
+    pub(crate) axis_position: Point,
+    pub(crate) axis_value: (f32, f32),
+    pub(crate) trigger_value: f32
 }
 
 impl InputInfo {
@@ -15,8 +20,13 @@ impl InputInfo {
             press_start: false,
             pressing: false,
             pressed: false,
+            repeat: false,
             mouse_position: Point::new(-1, -1),
-            axis_position: Point::new(0, 0)
+            mouse_motion: Point::new(0, 0),
+            wheel_delta: Point::new(0, 0),
+            axis_position: Point::new(0, 0),
+            axis_value: (0.0, 0.0),
+            trigger_value: 0.0
         }
     }
 
@@ -32,14 +42,43 @@ impl InputInfo {
         self.pressed
     }
 
+    pub fn is_repeat(&self) -> bool {
+        self.repeat
+    }
+
     pub fn get_mouse_position(&self) -> Point {
         self.mouse_position.clone()
     }
 
+    pub fn get_mouse_motion(&self) -> Point {
+        self.mouse_motion.clone()
+    }
+
+    pub fn get_wheel_delta(&self) -> Point {
+        self.wheel_delta.clone()
+    }
+
     pub fn get_axis_postiion(&self) -> Point {
         self.axis_position.clone()
     }
 
+    pub fn get_axis_value(&self) -> (f32, f32) {
+        self.axis_value
+    }
+
+    pub fn get_trigger_value(&self) -> f32 {
+        self.trigger_value
+    }
+
+    pub(crate) fn update_axis_value(&mut self, value: (f32, f32)) {
+        let (current, next) = (self.axis_value.0.hypot(self.axis_value.1), value.0.hypot(value.1));
+        if next >= current { self.axis_value = value; }
+    }
+
+    pub(crate) fn update_trigger_value(&mut self, value: f32) {
+        if value > self.trigger_value { self.trigger_value = value; }
+    }
+
     pub(crate) fn update_press_state(&mut self, status: (bool, bool, bool)) {
         self.press_start = self.press_start || status.0;
         self.pressing = self.pressing || status.1;
@@ -0,0 +1,77 @@
+use std::fs::{ File, create_dir_all };
+use std::io::{ BufWriter, Write };
+use std::path::{ Path, PathBuf };
+use ::util::{ RandSnapshot, load_file };
+use ::util::parameter::{ InputEvent };
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct InputFrame {
+    pub delta: i64,
+    pub elapsed: i64,
+    pub events: Vec<InputEvent>
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct InputRecording {
+    pub rand: RandSnapshot,
+    pub frames: Vec<InputFrame>
+}
+
+impl InputRecording {
+
+    pub fn new(rand: RandSnapshot) -> Self {
+        Self {
+            rand: rand,
+            frames: Vec::new()
+        }
+    }
+
+    pub fn push_frame(&mut self, delta: i64, elapsed: i64, events: Vec<InputEvent>) {
+        self.frames.push(InputFrame {
+            delta: delta,
+            elapsed: elapsed,
+            events: events
+        });
+    }
+
+    pub fn load<A>(path: A) -> Result<Self, String> where A: AsRef<Path> {
+        let data = load_file(&PathBuf::from(path.as_ref()))?;
+        serde_json::from_slice(data.as_slice()).map_err(|e| e.to_string())
+    }
+
+    pub fn save<A>(&self, path: A) -> Result<(), String> where A: AsRef<Path> {
+        let p = path.as_ref();
+        if let Some(parent) = p.parent() {
+            create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let data = serde_json::to_vec(self).map_err(|e| e.to_string())?;
+        let mut f = BufWriter::new(File::create(p).map_err(|e| e.to_string())?);
+        f.write_all(data.as_slice()).map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::HashMap;
+    use super::*;
+
+    #[test]
+    fn frames_round_trip_with_elapsed() {
+        let mut recording = InputRecording::new(RandSnapshot { seed: 1, streams: HashMap::new() });
+        recording.push_frame(16, 33, Vec::new());
+        let data = serde_json::to_vec(&recording).unwrap();
+        let loaded: InputRecording = serde_json::from_slice(data.as_slice()).unwrap();
+        assert_eq!(loaded.frames[0].delta, 16);
+        assert_eq!(loaded.frames[0].elapsed, 33);
+    }
+
+    #[test]
+    fn frames_without_elapsed_are_rejected() {
+        let data = br#"{"rand":{"seed":1,"streams":{}},"frames":[{"delta":16,"events":[]}]}"#;
+        assert!(serde_json::from_slice::<InputRecording>(data).is_err());
+    }
+
+}
@@ -9,7 +9,18 @@ mod texture_query;
 mod input_code;
 mod input_code_map;
 mod input_info;
+mod input_event;
+mod input_recording;
+mod text_input_info;
+mod pointer_event;
+mod touch_point;
+mod touch_gesture;
+mod press_state;
+mod stick_direction;
 mod music_option;
+mod se_option;
+mod sound_bus;
+mod golden_image_option;
 mod opacity;
 mod scale;
 mod rotation;
@@ -23,9 +34,20 @@ pub use self::rect::*;
 pub use self::circle::*;
 pub use self::texture_query::*;
 pub use self::input_code::*;
-pub(crate) use self::input_code_map::*;
+pub use self::input_code_map::*;
 pub use self::input_info::*;
+pub use self::input_event::*;
+pub use self::input_recording::*;
+pub use self::text_input_info::*;
+pub use self::pointer_event::*;
+pub use self::touch_point::*;
+pub use self::touch_gesture::*;
+pub use self::press_state::*;
+pub use self::stick_direction::*;
 pub use self::music_option::*;
+pub use self::se_option::*;
+pub use self::sound_bus::*;
+pub use self::golden_image_option::*;
 pub use self::opacity::*;
 pub use self::scale::*;
 pub use self::rotation::*;
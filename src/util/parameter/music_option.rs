@@ -1,9 +1,13 @@
 use ::util::NoOption;
 
+#[derive(Clone)]
 pub struct MusicOption {
     pub loops: i32,
     pub fade_in: Option<i32>,
-    pub position: Option<f64>
+    pub position: Option<f64>,
+    pub crossfade: Option<i32>,
+    pub loop_start: Option<f64>,
+    pub loop_end: Option<f64>
 }
 
 impl Default for MusicOption {
@@ -12,7 +16,10 @@ impl Default for MusicOption {
         Self {
             loops: 0,
             fade_in: None,
-            position: None
+            position: None,
+            crossfade: None,
+            loop_start: None,
+            loop_end: None
         }
     }
 
@@ -0,0 +1,71 @@
+use std::cell::{ Cell, RefCell };
+use ::node::{ NodeId };
+use ::util::parameter::{ Point };
+
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+pub enum PointerEventType {
+    Enter,
+    Leave,
+    Click,
+    DragStart,
+    Drag,
+    Drop
+}
+
+pub struct PointerEvent {
+    pub(crate) event_type: PointerEventType,
+    pub(crate) target: NodeId,
+    pub(crate) position: Point,
+    pub(crate) start_position: Point,
+    pub(crate) local_position: RefCell<Point>,
+    pub(crate) drag_source: Option<NodeId>,
+    pub(crate) stopped: Cell<bool>
+}
+
+impl PointerEvent {
+
+    pub fn new(event_type: PointerEventType, target: NodeId, position: Point, start_position: Point, drag_source: Option<NodeId>) -> Self {
+        Self {
+            event_type: event_type,
+            target: target,
+            position: position.clone(),
+            start_position: start_position,
+            local_position: RefCell::new(position),
+            drag_source: drag_source,
+            stopped: Cell::new(false)
+        }
+    }
+
+    pub fn get_event_type(&self) -> PointerEventType {
+        self.event_type
+    }
+
+    pub fn get_target(&self) -> NodeId {
+        self.target.clone()
+    }
+
+    pub fn get_position(&self) -> Point {
+        self.position.clone()
+    }
+
+    pub fn get_start_position(&self) -> Point {
+        self.start_position.clone()
+    }
+
+    pub fn get_local_position(&self) -> Point {
+        self.local_position.borrow().clone()
+    }
+
+    pub fn get_drag_source(&self) -> Option<NodeId> {
+        self.drag_source.clone()
+    }
+
+    pub fn stop_propagation(&self) {
+        self.stopped.set(true);
+    }
+
+    pub fn is_propagation_stopped(&self) -> bool {
+        self.stopped.get()
+    }
+
+}
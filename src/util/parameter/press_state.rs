@@ -0,0 +1,5 @@
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+pub enum PressState {
+    Pressed,
+    Released
+}
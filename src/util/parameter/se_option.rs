@@ -0,0 +1,39 @@
+use ::util::NoOption;
+
+#[derive(Clone)]
+pub struct SEOption {
+    pub volume: f32,
+    pub priority: i32,
+    pub max_instances: Option<usize>
+}
+
+impl Default for SEOption {
+
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            priority: 0,
+            max_instances: None
+        }
+    }
+
+}
+
+impl From<f32> for SEOption {
+
+    fn from(f: f32) -> Self {
+        Self {
+            volume: f,
+            ..Default::default()
+        }
+    }
+
+}
+
+impl From<NoOption> for SEOption {
+
+    fn from(_: NoOption) -> Self {
+        SEOption::default()
+    }
+
+}
@@ -0,0 +1,7 @@
+#[derive(Clone, Copy, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub enum SoundBus {
+    Master,
+    BGM,
+    SE,
+    Voice
+}
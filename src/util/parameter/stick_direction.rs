@@ -0,0 +1,7 @@
+#[derive(Clone, Copy, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub enum StickDirection {
+    Up,
+    Down,
+    Left,
+    Right
+}
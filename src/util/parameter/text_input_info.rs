@@ -0,0 +1,40 @@
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct TextInputInfo {
+    pub(crate) committed: String,
+    pub(crate) composition: String,
+    pub(crate) cursor: i32,
+    pub(crate) backspaces: u32
+}
+
+impl TextInputInfo {
+
+    pub fn new() -> Self {
+        Self {
+            committed: String::new(),
+            composition: String::new(),
+            cursor: 0,
+            backspaces: 0
+        }
+    }
+
+    pub fn get_committed(&self) -> String {
+        self.committed.clone()
+    }
+
+    pub fn get_composition(&self) -> String {
+        self.composition.clone()
+    }
+
+    pub fn get_cursor(&self) -> i32 {
+        self.cursor
+    }
+
+    pub fn get_backspaces(&self) -> u32 {
+        self.backspaces
+    }
+
+    pub fn is_composing(&self) -> bool {
+        !self.composition.is_empty()
+    }
+
+}
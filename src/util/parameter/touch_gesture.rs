@@ -0,0 +1,9 @@
+use ::util::parameter::{ Point, StickDirection };
+
+#[derive(Clone, PartialEq)]
+pub enum TouchGesture {
+    Tap { position: Point },
+    LongPress { position: Point },
+    Swipe { start: Point, end: Point, direction: StickDirection },
+    Pinch { center: Point, scale: f32, rotation: f32 }
+}
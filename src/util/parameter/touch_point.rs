@@ -0,0 +1,39 @@
+use ::util::parameter::{ Point };
+
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct TouchPoint {
+    pub(crate) id: i64,
+    pub(crate) position: Point,
+    pub(crate) start_position: Point,
+    pub(crate) press_start: bool,
+    pub(crate) pressed: bool,
+    pub(crate) duration: i64
+}
+
+impl TouchPoint {
+
+    pub fn get_id(&self) -> i64 {
+        self.id
+    }
+
+    pub fn get_position(&self) -> Point {
+        self.position.clone()
+    }
+
+    pub fn get_start_position(&self) -> Point {
+        self.start_position.clone()
+    }
+
+    pub fn is_press_start(&self) -> bool {
+        self.press_start
+    }
+
+    pub fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+
+    pub fn get_duration(&self) -> i64 {
+        self.duration
+    }
+
+}
@@ -1,6 +1,6 @@
 use std::env;
 use std::path::PathBuf;
-use std::fs::File;
+use std::fs::{ File, OpenOptions };
 use std::rc::Rc;
 use std::io::{BufReader, Read, Write, stdout};
 use ::application::{ Application, Context };
@@ -104,9 +104,81 @@ pub(crate) fn with_context<T, R>(callback: T) -> R where T: FnOnce(&'static mut
     }
 }
 
-fn initialize_context(application: Rc<dyn Application>) {
+fn initialize_context(application: Rc<dyn Application>, headless: bool) -> Result<(), String> {
+    let context = Context::new_with_mode(application, headless)?;
     unsafe {
-        ::CONTEXT = Some(Context::new(application));
+        ::CONTEXT = Some(context);
+    }
+    Ok(())
+}
+
+pub(crate) fn initialize_application(application: Rc<dyn Application>, headless: bool) -> Result<(), String> {
+    director::set_application(application.clone());
+    initialize_context(application.clone(), headless)?;
+    director::initialize_audio();
+    director::replace_scene(application.application_did_finish_launching(), ::NoOption);
+    director::apply_all_config();
+    director::get_scene().start_update();
+    Ok(())
+}
+
+pub(crate) fn release_application() {
+    director::reset();
+    unsafe {
+        ::CONTEXT = None;
+    }
+}
+
+pub(crate) fn prepare_frame() {
+    let event_pump = with_context(|c| &mut c.event_pump);
+    director::poll_input(event_pump);
+}
+
+pub(crate) fn update_frame() {
+    director::update_input_state();
+    if director::is_suspended() { return; }
+    director::dispatch_pointer_events();
+    let scene = director::get_scene();
+    scene.start_update();
+    let next_scene = director::get_scene();
+    if next_scene.inner_id() != scene.inner_id() { next_scene.start_update(); }
+}
+
+pub(crate) fn render_frame(alpha: f32) {
+    let (scene, prev_scene, transition) = (
+        director::get_scene(), director::get_prev_scene(), director::get_scene_transition()
+    );
+    director::update_resolution_size();
+    scene.start_render(alpha);
+    if let Some(p) = prev_scene.clone() {
+        p.start_render(alpha);
+    }
+    let status = director::render_canvas(scene.clone(), prev_scene.clone(), transition.clone());
+    if status == TransitionStatus::Finish && prev_scene.is_some() {
+        director::destroy_prev_scene();
+    }
+}
+
+pub(crate) fn finish_frame(application: Rc<dyn Application>) {
+    director::clean_se();
+    director::update_music();
+    director::update_voice();
+    if director::is_quit() {
+        application.on_quit();
+    }
+}
+
+pub(crate) fn output_warning_log(message: &str) {
+    let text = format!("[Warning ({})] {}\n", Local::now(), message);
+    match build_mode() {
+        BuildMode::Release => {
+            let mut dir = exe_dir();
+            dir.push("application.log");
+            if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(dir) {
+                file.write_all(text.as_bytes()).ok();
+            }
+        },
+        BuildMode::Development => { print!("{}", text); }
     }
 }
 
@@ -139,46 +211,20 @@ fn set_panic_hook() {
 
 pub fn run(application: Rc<dyn Application>) {
     set_panic_hook();
-    director::set_application(application.clone());
-    initialize_context(application.clone());
-    let event_pump = with_context(|c| &mut c.event_pump);
+    initialize_application(application.clone(), application.headless()).unwrap();
     let mut fps_manager = FpsManager::new(application.fps());
-    director::replace_scene(application.application_did_finish_launching(), ::NoOption);
-    director::get_scene().start_update();
+    fps_manager.set_step_mode(application.step_mode());
     let mut prev_sleep_time: i64 = 0;
     while director::is_continuing() {
-        let (scene, mut prev_scene, transition) = (
-            director::get_scene(), director::get_prev_scene(), director::get_scene_transition()
-        );
         prev_sleep_time = fps_manager.run(
             prev_sleep_time,
-            || {
-                director::update_input_state(event_pump);
-            },
-            || {
-                scene.start_update();
-                let next_scene = director::get_scene();
-                if next_scene.inner_id() != scene.inner_id() { next_scene.start_update(); }
-            },
-            || {
-                director::update_resolution_size();
-                scene.start_render();
-                if let Some(p) = prev_scene.clone() {
-                    p.start_render();
-                }
-                let status = director::render_canvas(scene.clone(), prev_scene.clone(), transition.clone());
-                if status == TransitionStatus::Finish && prev_scene.is_some() {
-                    director::destroy_prev_scene();
-                    prev_scene = None;
-                }
-            }
+            || prepare_frame(),
+            || update_frame(),
+            |alpha| render_frame(alpha)
         );
         director::set_current_fps(fps_manager.fps());
-        director::clean_se();
-        if director::is_quit() {
-            application.on_quit();
-        }
+        finish_frame(application.clone());
     }
-    sdl2::mixer::close_audio();
+    director::flush_config();
+    with_context(|c| c.close_audio());
 }
-
@@ -0,0 +1,84 @@
+extern crate rgengine;
+
+use std::rc::Rc;
+use std::cell::Cell;
+use std::sync::{ Mutex, MutexGuard };
+use rgengine::application::{ Application, WindowMode };
+use rgengine::node::{ Node };
+use rgengine::node::scene::{ Scene, SceneLike };
+use rgengine::util::{ HeadlessRunner, director };
+
+static RUNNER_LOCK: Mutex<()> = Mutex::new(());
+
+thread_local! {
+    static UPDATES: Cell<u32> = Cell::new(0);
+}
+
+fn lock_runner() -> MutexGuard<'static, ()> {
+    RUNNER_LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn updates() -> u32 {
+    UPDATES.with(|u| u.get())
+}
+
+struct CountingScene {}
+
+impl Scene for CountingScene {
+
+    fn update_scene(&self) {
+        UPDATES.with(|u| u.set(u.get() + 1));
+    }
+
+}
+
+struct TestApplication {}
+
+impl Application for TestApplication {
+
+    fn application_did_finish_launching(&self) -> Rc<dyn SceneLike> {
+        Node::create(CountingScene {})
+    }
+
+    fn title(&self) -> String { "headless test".to_owned() }
+
+    fn headless(&self) -> bool { true }
+
+}
+
+#[test]
+fn runners_can_be_created_one_after_another() {
+    let _lock = lock_runner();
+    let mut first = HeadlessRunner::new(Rc::new(TestApplication {})).unwrap();
+    assert!(first.step_frames(3));
+    assert_eq!(first.frame(), 3);
+    assert!(HeadlessRunner::new(Rc::new(TestApplication {})).is_err());
+    drop(first);
+
+    let mut second = HeadlessRunner::new(Rc::new(TestApplication {})).unwrap();
+    assert!(second.step_frames(2));
+    assert_eq!(second.frame(), 2);
+}
+
+#[test]
+fn game_pause_freezes_the_clock_but_keeps_updating_the_scene() {
+    let _lock = lock_runner();
+    let mut runner = HeadlessRunner::new(Rc::new(TestApplication {})).unwrap();
+    director::set_paused(true);
+    let (before, clock) = (updates(), director::get_clock_time());
+    assert!(runner.step_frames(2));
+    assert_eq!(updates(), before + 2);
+    assert_eq!(director::get_clock_time(), clock);
+
+    director::set_paused(false);
+    assert!(runner.step());
+    assert!(director::get_clock_time() > clock);
+}
+
+#[test]
+fn window_mode_changes_are_saved_to_the_config() {
+    let _lock = lock_runner();
+    let _runner = HeadlessRunner::new(Rc::new(TestApplication {})).unwrap();
+    director::set_window_mode(WindowMode::Windowed).unwrap();
+    assert!(director::get_config::<WindowMode>(director::CONFIG_WINDOW_MODE) == Some(WindowMode::Windowed));
+}